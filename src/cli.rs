@@ -1,4 +1,8 @@
-use clap::{Parser, Subcommand};
+use crate::commands::sbom::SbomFormat;
+use crate::env::hooks::HookKind;
+use crate::env::paths::list_env_names;
+use clap::{Args, Parser, Subcommand};
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
 
 #[derive(Parser)]
 #[command(name = "uvup")]
@@ -9,16 +13,31 @@ pub(crate) struct Cli {
     pub(crate) command: Commands,
 }
 
+/// The `--env` option shared by package commands
+#[derive(Args)]
+pub(crate) struct EnvArgs {
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Environment to operate on (default: $UVUP_ENV, then the active environment)",
+        add = env_name_completer()
+    )]
+    pub(crate) env: Option<String>,
+}
+
 #[derive(Subcommand)]
 pub(crate) enum Commands {
     #[command(about = "Initialize uvup shell integration")]
     Init {
         #[arg(
-            help = "Shell to initialize (powershell, bash, zsh, fish). If not specified, initializes all detected shells"
+            help = "Shell to initialize (powershell, bash, zsh, fish, nushell, tcsh, xonsh). If not specified, initializes all detected shells"
         )]
         shell: Option<String>,
 
-        #[arg(long, help = "Print shell script instead of installing")]
+        #[arg(
+            long,
+            help = "Print the hook script for the given shell (default: current shell) instead of installing"
+        )]
         raw: bool,
 
         #[arg(long, help = "Remove uvup initialization")]
@@ -26,6 +45,104 @@ pub(crate) enum Commands {
 
         #[arg(long, help = "Show what would be done without making changes")]
         dry_run: bool,
+
+        #[arg(long, help = "Also install shell completions")]
+        completions: bool,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["raw", "reverse", "dry_run", "completions"],
+            help = "Report which shells have uvup initialized"
+        )]
+        status: bool,
+
+        #[arg(long, hide = true)]
+        refresh_hooks: bool,
+    },
+
+    #[command(about = "Generate shell completions")]
+    Completions {
+        #[arg(help = "Shell to generate completions for (bash, zsh, fish, powershell)")]
+        shell: String,
+    },
+
+    #[command(about = "Activate an environment (requires shell integration)")]
+    Activate {
+        #[arg(help = "Name of the environment to activate", add = env_name_completer())]
+        name: String,
+
+        #[arg(
+            long,
+            help = "Keep the current environment on a stack so 'uvup deactivate' returns to it"
+        )]
+        stack: bool,
+    },
+
+    #[command(
+        about = "Deactivate the current environment, returning to a stacked one (requires shell integration)"
+    )]
+    Deactivate,
+
+    #[command(about = "Print shell code that activates an environment")]
+    ActivateScript {
+        #[arg(help = "Name of the environment to activate", add = env_name_completer())]
+        name: String,
+
+        #[arg(
+            long,
+            help = "Shell to generate code for (powershell, bash, zsh, fish, nushell, tcsh, xonsh). Defaults to the detected shell"
+        )]
+        shell: Option<String>,
+
+        #[arg(
+            long,
+            help = "Keep the current environment on a stack so 'uvup deactivate' returns to it"
+        )]
+        stack: bool,
+    },
+
+    #[command(about = "Print shell code that deactivates the current environment")]
+    DeactivateScript {
+        #[arg(
+            long,
+            help = "Shell to generate code for (powershell, bash, zsh, fish, nushell, tcsh, xonsh). Defaults to the detected shell"
+        )]
+        shell: Option<String>,
+    },
+
+    #[command(about = "Manage variables exported while an environment is active")]
+    EnvVars {
+        #[command(subcommand)]
+        command: EnvVarsCommand,
+    },
+
+    #[command(about = "Manage scripts sourced on activation and deactivation")]
+    Hooks {
+        #[command(subcommand)]
+        command: HooksCommand,
+    },
+
+    #[command(about = "Run a command inside an environment without activating it")]
+    Run {
+        #[arg(help = "Name of the environment", add = env_name_completer())]
+        name: String,
+
+        #[arg(
+            required = true,
+            trailing_var_arg = true,
+            allow_hyphen_values = true,
+            help = "Command to run, with its arguments"
+        )]
+        command: Vec<String>,
+    },
+
+    #[command(about = "Print the active environment for use in shell prompts")]
+    Prompt {
+        #[arg(
+            long,
+            help = "Output format; placeholders: {env}, {python}, {stale} (default: \"{env} py{python}{stale}\")"
+        )]
+        format: Option<String>,
     },
 
     #[command(about = "Create a new virtual environment")]
@@ -42,25 +159,37 @@ pub(crate) enum Commands {
 
     #[command(about = "Delete an environment")]
     Delete {
-        #[arg(help = "Name of the environment to delete")]
+        #[arg(help = "Name of the environment to delete", add = env_name_completer())]
         name: String,
     },
 
     #[command(about = "Clone an environment (exact 1:1 copy)")]
     Clone {
-        #[arg(help = "Source environment name")]
+        #[arg(help = "Source environment name", add = env_name_completer())]
         source: String,
 
         #[arg(help = "Target environment name")]
         target: String,
     },
 
+    #[command(about = "Compare the dependencies of two environments")]
+    Diff {
+        #[arg(help = "Environment to compare from", add = env_name_completer())]
+        env_a: String,
+
+        #[arg(help = "Environment to compare to", add = env_name_completer())]
+        env_b: String,
+
+        #[arg(long, help = "Print the differences as JSON")]
+        json: bool,
+    },
+
     #[command(about = "Create a new project from a template")]
     New {
         #[arg(help = "Project name")]
         name: String,
 
-        #[arg(long, help = "Template environment name")]
+        #[arg(long, help = "Template environment name", add = env_name_completer())]
         template: String,
 
         #[arg(short, long, help = "Python version (override template version)")]
@@ -89,7 +218,7 @@ pub(crate) enum Commands {
 
     #[command(about = "Sync current project with a template")]
     Sync {
-        #[arg(long, help = "Template environment name")]
+        #[arg(long, help = "Template environment name", add = env_name_completer())]
         template: String,
 
         #[arg(short, long, help = "Python version (override current version)")]
@@ -124,8 +253,30 @@ pub(crate) enum Commands {
         #[arg(help = "Packages to add", required = true)]
         packages: Vec<String>,
 
-        #[arg(long, help = "Add to optional dependency group")]
+        #[arg(
+            long,
+            value_name = "EXTRA",
+            help = "Add to an extra in [project.optional-dependencies]"
+        )]
+        optional: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with = "optional",
+            help = "Add to a group in [dependency-groups]"
+        )]
         group: Option<String>,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["optional", "group"],
+            help = "Add to the 'dev' group in [dependency-groups]"
+        )]
+        dev: bool,
+
+        #[command(flatten)]
+        env: EnvArgs,
     },
 
     #[command(about = "Remove packages from the active environment")]
@@ -133,19 +284,314 @@ pub(crate) enum Commands {
         #[arg(help = "Packages to remove", required = true)]
         packages: Vec<String>,
 
-        #[arg(long, help = "Remove from optional dependency group")]
+        #[arg(
+            long,
+            value_name = "EXTRA",
+            help = "Remove from an extra in [project.optional-dependencies]"
+        )]
+        optional: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with = "optional",
+            help = "Remove from a group in [dependency-groups]"
+        )]
         group: Option<String>,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["optional", "group"],
+            help = "Remove from the 'dev' group in [dependency-groups]"
+        )]
+        dev: bool,
+
+        #[command(flatten)]
+        env: EnvArgs,
     },
 
     #[command(about = "Update the lockfile of the active environment")]
     Lock {
         #[arg(long, help = "Update all packages to their latest versions")]
         upgrade: bool,
+
+        #[command(flatten)]
+        env: EnvArgs,
+    },
+
+    #[command(about = "List the resolved packages of an environment")]
+    Packages {
+        #[arg(
+            conflicts_with = "env",
+            help = "Name of the environment (default: --env, $UVUP_ENV, then the active environment)",
+            add = env_name_completer()
+        )]
+        name: Option<String>,
+
+        #[arg(long, help = "Print the packages as JSON")]
+        json: bool,
+
+        #[command(flatten)]
+        env: EnvArgs,
+    },
+
+    #[command(about = "Check locked packages against a local OSV advisory database")]
+    Audit {
+        #[arg(long, conflicts_with = "env", help = "Audit every environment")]
+        all: bool,
+
+        #[arg(
+            long,
+            value_name = "DIR",
+            help = "Directory of OSV JSON advisories (e.g. an extracted PyPI export)"
+        )]
+        db: String,
+
+        #[arg(
+            long,
+            value_name = "ID",
+            value_delimiter = ',',
+            help = "Advisory ID or alias to accept without failing (repeatable)"
+        )]
+        ignore: Vec<String>,
+
+        #[arg(long, help = "Print the findings as JSON")]
+        json: bool,
+
+        #[command(flatten)]
+        env: EnvArgs,
+    },
+
+    #[command(about = "Show which dependencies require a package")]
+    Why {
+        #[arg(help = "Package name")]
+        package: String,
+
+        #[arg(long, help = "Print the dependency paths as JSON")]
+        json: bool,
+
+        #[command(flatten)]
+        env: EnvArgs,
+    },
+
+    #[command(about = "List locked packages with newer versions on the package index")]
+    Outdated {
+        #[arg(long, conflicts_with = "env", help = "Check every environment")]
+        all: bool,
+
+        #[arg(
+            long,
+            env = "UV_DEFAULT_INDEX",
+            value_name = "URL",
+            help = "Simple API URL of the index to check (default: the index recorded in uv.lock)"
+        )]
+        index_url: Option<String>,
+
+        #[arg(long, help = "Print the outdated packages as JSON")]
+        json: bool,
+
+        #[command(flatten)]
+        env: EnvArgs,
+    },
+
+    #[command(about = "Group the packages installed in an environment by license")]
+    Licenses {
+        #[arg(
+            conflicts_with = "env",
+            help = "Name of the environment (default: --env, $UVUP_ENV, then the active environment)",
+            add = env_name_completer()
+        )]
+        name: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "LICENSE",
+            value_delimiter = ',',
+            help = "Fail if a package's license matches (e.g. GPL; repeatable)"
+        )]
+        deny: Vec<String>,
+
+        #[arg(long, help = "Print the licenses as JSON")]
+        json: bool,
+
+        #[command(flatten)]
+        env: EnvArgs,
+    },
+
+    #[command(about = "Print a software bill of materials for an environment")]
+    Sbom {
+        #[arg(
+            conflicts_with = "env",
+            help = "Name of the environment (default: --env, $UVUP_ENV, then the active environment)",
+            add = env_name_completer()
+        )]
+        name: Option<String>,
+
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = SbomFormat::CycloneDxJson,
+            help = "SBOM format"
+        )]
+        format: SbomFormat,
+
+        #[command(flatten)]
+        env: EnvArgs,
+    },
+
+    #[command(about = "Find the environments that contain a package")]
+    SearchInstalled {
+        #[arg(help = "Package name")]
+        package: String,
+
+        #[arg(
+            long,
+            value_name = "SPEC",
+            help = "Only show versions matching a PEP 440 specifier (e.g. '>=2,<3')"
+        )]
+        version: Option<String>,
+
+        #[arg(long, help = "Also look at the packages installed in each .venv")]
+        installed: bool,
+
+        #[arg(long, help = "Print the matches as JSON")]
+        json: bool,
     },
 
     #[command(about = "Display the dependency tree of the active environment")]
     Tree {
         #[arg(long, help = "Maximum depth to display")]
         depth: Option<usize>,
+
+        #[command(flatten)]
+        env: EnvArgs,
     },
+
+    #[command(
+        about = "Save pyproject.toml and uv.lock of an environment",
+        args_conflicts_with_subcommands = true,
+        subcommand_negates_reqs = true
+    )]
+    Snapshot {
+        #[command(subcommand)]
+        command: Option<SnapshotCommand>,
+
+        #[arg(help = "Name of the environment", required = true, add = env_name_completer())]
+        name: Option<String>,
+
+        #[arg(help = "Label to refer to the snapshot by")]
+        label: Option<String>,
+    },
+
+    #[command(about = "Restore an environment from a snapshot and re-sync it")]
+    Rollback {
+        #[arg(help = "Name of the environment", add = env_name_completer())]
+        name: String,
+
+        #[arg(help = "Snapshot id or label (the most recent snapshot with that label)")]
+        snapshot: String,
+    },
+
+    #[command(about = "Revert the last add or remove in the active environment")]
+    Undo {
+        #[command(flatten)]
+        env: EnvArgs,
+    },
+
+    #[command(about = "Show the journal of changes made to an environment")]
+    History {
+        #[arg(
+            conflicts_with = "env",
+            help = "Name of the environment (default: --env, $UVUP_ENV, then the active environment)",
+            add = env_name_completer()
+        )]
+        name: Option<String>,
+
+        #[arg(long, help = "Print the raw JSON lines")]
+        json: bool,
+
+        #[command(flatten)]
+        env: EnvArgs,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum EnvVarsCommand {
+    #[command(about = "Set variables for an environment")]
+    Set {
+        #[arg(help = "Name of the environment", add = env_name_completer())]
+        name: String,
+
+        #[arg(required = true, value_name = "KEY=VALUE", help = "Variables to set")]
+        vars: Vec<String>,
+    },
+
+    #[command(about = "Remove variables from an environment")]
+    Unset {
+        #[arg(help = "Name of the environment", add = env_name_completer())]
+        name: String,
+
+        #[arg(required = true, value_name = "KEY", help = "Variables to remove")]
+        keys: Vec<String>,
+    },
+
+    #[command(about = "List the variables of an environment")]
+    List {
+        #[arg(help = "Name of the environment", add = env_name_completer())]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum HooksCommand {
+    #[command(about = "List the hook scripts of an environment")]
+    List {
+        #[arg(help = "Name of the environment", add = env_name_completer())]
+        name: String,
+    },
+
+    #[command(about = "Edit a hook script, creating it if needed")]
+    Edit {
+        #[arg(help = "Name of the environment", add = env_name_completer())]
+        name: String,
+
+        #[arg(
+            value_enum,
+            help = "Whether the script runs after activation or before deactivation"
+        )]
+        kind: HookKind,
+
+        #[arg(
+            long,
+            help = "Shell flavour of the script (powershell, bash, zsh, fish, tcsh, xonsh). Defaults to the detected shell"
+        )]
+        shell: Option<String>,
+
+        #[arg(
+            long = "name",
+            default_value = "default",
+            help = "Script file name, without extension"
+        )]
+        script_name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum SnapshotCommand {
+    #[command(about = "List the snapshots of an environment")]
+    List {
+        #[arg(help = "Name of the environment", add = env_name_completer())]
+        name: String,
+    },
+}
+
+/// Complete argument values with the names of existing environments
+fn env_name_completer() -> ArgValueCandidates {
+    ArgValueCandidates::new(|| {
+        list_env_names()
+            .unwrap_or_default()
+            .into_iter()
+            .map(CompletionCandidate::new)
+            .collect()
+    })
 }
@@ -0,0 +1,86 @@
+use crate::env::hooks::{self, HookKind};
+use crate::env::paths::{get_venv_path, validate_env_name};
+use crate::env::stack::{ENV_STACK_VAR, read_stack, stack_value};
+use crate::env::vars;
+use crate::error::{Result, UvupError};
+use crate::shell::detect::{ShellType, detect_shell, parse_shell_type};
+use crate::shell::script::{Action, render, venv_activate_script};
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Print shell code that activates an environment, meant to be evaluated by the shell
+pub(crate) fn run(name: String, shell: Option<&str>, stack: bool) -> Result<()> {
+    validate_env_name(&name)?;
+
+    let shell_type = match shell {
+        Some(shell_name) => parse_shell_type(shell_name)?,
+        None => detect_shell()?,
+    };
+
+    let venv_path = get_venv_path(&name)?;
+    if !venv_activate_script(shell_type, &venv_path).exists() {
+        return Err(UvupError::EnvNotFound(name));
+    }
+
+    let mut actions = hook_script_actions(HookKind::Deactivate, shell_type)?;
+    actions.extend(activation_actions(&name, venv_path, stack)?);
+    actions.extend(
+        hooks::shell_scripts(&name, HookKind::Activate, shell_type)?
+            .into_iter()
+            .map(Action::SourceScript),
+    );
+
+    io::stdout().write_all(render(shell_type, &actions).as_bytes())?;
+    Ok(())
+}
+
+/// Build the actions sourcing the active uvup environment's hook scripts of one kind
+pub(crate) fn hook_script_actions(kind: HookKind, shell: ShellType) -> Result<Vec<Action>> {
+    let Some(active_env) = env::var("UVUP_ACTIVE_ENV")
+        .ok()
+        .filter(|name| validate_env_name(name).is_ok())
+    else {
+        return Ok(Vec::new());
+    };
+
+    Ok(hooks::shell_scripts(&active_env, kind, shell)?
+        .into_iter()
+        .map(Action::SourceScript)
+        .collect())
+}
+
+/// Build the actions that switch from the current venv to an environment
+///
+/// With `stack`, the currently active venv is pushed onto the activation stack
+/// so that `uvup deactivate` returns to it.
+pub(crate) fn activation_actions(
+    name: &str,
+    venv_path: PathBuf,
+    stack: bool,
+) -> Result<Vec<Action>> {
+    let mut actions = vars::restore_actions();
+    actions.push(Action::DeactivateVenv);
+
+    if stack {
+        if let Some(active_venv) = env::var_os("VIRTUAL_ENV").map(PathBuf::from) {
+            if active_venv != venv_path {
+                let mut entries = read_stack();
+                entries.push(active_venv);
+                actions.push(Action::SetVar(
+                    ENV_STACK_VAR.to_string(),
+                    stack_value(&entries)?,
+                ));
+            }
+        }
+    }
+
+    actions.push(Action::ActivateVenv(venv_path));
+    actions.push(Action::SetVar(
+        "UVUP_ACTIVE_ENV".to_string(),
+        name.to_string(),
+    ));
+    actions.extend(vars::apply_actions(&vars::load(name)?));
+
+    Ok(actions)
+}
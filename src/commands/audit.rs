@@ -0,0 +1,180 @@
+use crate::env::lockfile::Lockfile;
+use crate::env::osv::Database;
+use crate::env::paths::{list_env_names, resolve_env};
+use crate::env::pep440::Version;
+use crate::error::{Result, UvupError};
+use crate::utils::{print_info, print_success, write_table};
+use serde_json::{Value, json};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// A locked package matched by an advisory
+struct Vulnerability {
+    env: String,
+    package: String,
+    version: String,
+    id: String,
+    aliases: Vec<String>,
+    summary: Option<String>,
+    severity: Option<String>,
+    cvss: Vec<String>,
+    fixed: Vec<String>,
+    ignored: bool,
+}
+
+/// Match locked packages against a local OSV advisory database
+///
+/// Fails when a finding is not ignored, so the exit code can gate CI.
+pub(crate) fn run(
+    name: Option<String>,
+    all: bool,
+    db_path: &Path,
+    ignore: &[String],
+    json: bool,
+) -> Result<()> {
+    let envs = if all {
+        list_env_names()?
+    } else {
+        vec![resolve_env(name)?]
+    };
+
+    let db = Database::load(db_path)?;
+    if db.skipped > 0 {
+        let _ = writeln!(
+            io::stderr(),
+            "Warning: Skipped {} file(s) in {} that are not OSV records",
+            db.skipped,
+            db_path.display()
+        );
+    }
+
+    let mut vulnerabilities = Vec::new();
+    let mut audited = 0;
+    for env in &envs {
+        let lock = match Lockfile::load(env) {
+            Ok(lock) => lock,
+            // With --all, environments that were never locked have nothing to audit
+            Err(UvupError::LockfileMissing(_)) if all => continue,
+            Err(e) => return Err(e),
+        };
+        audited += 1;
+
+        for package in lock.dependencies() {
+            let Some(version) = package.version.as_deref().and_then(Version::parse) else {
+                continue;
+            };
+            for finding in db.findings(&package.name, &version) {
+                let advisory = finding.advisory;
+                vulnerabilities.push(Vulnerability {
+                    env: env.clone(),
+                    package: package.name.clone(),
+                    version: version.to_string(),
+                    id: advisory.id.clone(),
+                    aliases: advisory.aliases.clone(),
+                    summary: advisory.summary.clone(),
+                    severity: advisory.severity.clone(),
+                    cvss: advisory.cvss.clone(),
+                    fixed: finding.fixed,
+                    ignored: ignore.iter().any(|id| advisory.is_known_as(id)),
+                });
+            }
+        }
+    }
+
+    let reported = vulnerabilities.iter().filter(|v| !v.ignored).count();
+    let ignored = vulnerabilities.len() - reported;
+
+    if json {
+        let rows: Vec<Value> = vulnerabilities
+            .iter()
+            .map(|v| {
+                json!({
+                    "env": v.env,
+                    "package": v.package,
+                    "version": v.version,
+                    "id": v.id,
+                    "aliases": v.aliases,
+                    "summary": v.summary,
+                    "severity": v.severity,
+                    "cvss": v.cvss,
+                    "fixed": v.fixed,
+                    "ignored": v.ignored,
+                })
+            })
+            .collect();
+        writeln!(io::stdout(), "{:#}", Value::from(rows))?;
+    } else {
+        print_report(&vulnerabilities, all, audited, ignored)?;
+    }
+
+    if reported > 0 {
+        return Err(UvupError::VulnerabilitiesFound(reported));
+    }
+    Ok(())
+}
+
+fn print_report(
+    vulnerabilities: &[Vulnerability],
+    all: bool,
+    audited: usize,
+    ignored: usize,
+) -> Result<()> {
+    let shown: Vec<&Vulnerability> = vulnerabilities.iter().filter(|v| !v.ignored).collect();
+    let ignored_note = if ignored > 0 {
+        format!(" ({ignored} ignored)")
+    } else {
+        String::new()
+    };
+
+    if shown.is_empty() {
+        let scope = if all {
+            format!("{audited} environment(s)")
+        } else {
+            "the environment".to_string()
+        };
+        print_success(&format!(
+            "No known vulnerabilities in {scope}{ignored_note}"
+        ));
+        return Ok(());
+    }
+
+    let mut header = vec!["PACKAGE", "VERSION", "ID", "SEVERITY", "FIXED"];
+    if all {
+        header.insert(0, "ENV");
+    }
+    let fixed: Vec<String> = shown
+        .iter()
+        .map(|v| {
+            if v.fixed.is_empty() {
+                "-".to_string()
+            } else {
+                v.fixed.join(", ")
+            }
+        })
+        .collect();
+    let rows: Vec<Vec<&str>> = shown
+        .iter()
+        .zip(&fixed)
+        .map(|(v, fixed)| {
+            let mut cells = vec![
+                v.package.as_str(),
+                v.version.as_str(),
+                v.id.as_str(),
+                v.severity.as_deref().unwrap_or("-"),
+                fixed.as_str(),
+            ];
+            if all {
+                cells.insert(0, v.env.as_str());
+            }
+            cells
+        })
+        .collect();
+    write_table(&mut io::stdout(), &header, &rows)?;
+
+    print_info(&format!(
+        "\nFound {} vulnerabilit{}{ignored_note}",
+        shown.len(),
+        if shown.len() == 1 { "y" } else { "ies" }
+    ));
+    Ok(())
+}
@@ -1,10 +1,14 @@
 // Allow println! in this module as it's used for user-facing output
 #![allow(clippy::print_stdout)]
 
+use crate::env::hooks;
+use crate::env::journal;
 use crate::env::paths::{get_env_path, validate_env_name};
+use crate::env::vars;
 use crate::error::{Result, UvupError};
 use crate::utils::print_success;
 use std::fs;
+use std::path::Path;
 use std::process::Command;
 
 /// Clone an environment (exact 1:1 copy without modifications)
@@ -27,8 +31,21 @@ pub(crate) fn run(source: String, target: String) -> Result<()> {
 
     println!("Cloning environment '{source}' to '{target}'...");
 
+    journal::record(&target, || {
+        clone_env(&source, &source_path, &target, &target_path)
+    })?;
+
+    print_success(&format!(
+        "Successfully cloned environment '{source}' to '{target}'"
+    ));
+
+    Ok(())
+}
+
+/// Copy the project files and recreate the venv, removing the target on failure
+fn clone_env(source: &str, source_path: &Path, target: &str, target_path: &Path) -> Result<()> {
     // Create target directory
-    fs::create_dir_all(&target_path)?;
+    fs::create_dir_all(target_path)?;
 
     // Copy pyproject.toml
     let source_toml = source_path.join("pyproject.toml");
@@ -52,18 +69,29 @@ pub(crate) fn run(source: String, target: String) -> Result<()> {
             .map_err(|e| UvupError::PathError(format!("Failed to copy uv.lock: {e}")))?;
     }
 
+    // Copy environment variables if set
+    let env_vars = vars::load(source)?;
+    if !env_vars.is_empty() {
+        vars::save(target, &env_vars)?;
+    }
+
+    // Copy activation and deactivation scripts
+    hooks::copy_scripts(source, target)?;
+
     // Create venv
     println!("Creating virtual environment...");
     let venv_status = Command::new("uv")
         .arg("venv")
-        .current_dir(&target_path)
+        .arg("--prompt")
+        .arg(target)
+        .current_dir(target_path)
         .status()
         .map_err(|e| {
             UvupError::CommandExecutionFailed(format!("Failed to execute uv venv: {e}"))
         })?;
 
     if !venv_status.success() {
-        let _ = fs::remove_dir_all(&target_path);
+        let _ = fs::remove_dir_all(target_path);
         return Err(UvupError::CommandExecutionFailed(
             "Failed to create virtual environment".to_string(),
         ));
@@ -73,22 +101,18 @@ pub(crate) fn run(source: String, target: String) -> Result<()> {
     println!("Installing packages...");
     let sync_status = Command::new("uv")
         .arg("sync")
-        .current_dir(&target_path)
+        .current_dir(target_path)
         .status()
         .map_err(|e| {
             UvupError::CommandExecutionFailed(format!("Failed to execute uv sync: {e}"))
         })?;
 
     if !sync_status.success() {
-        let _ = fs::remove_dir_all(&target_path);
+        let _ = fs::remove_dir_all(target_path);
         return Err(UvupError::CommandExecutionFailed(
             "Failed to install packages".to_string(),
         ));
     }
 
-    print_success(&format!(
-        "Successfully cloned environment '{source}' to '{target}'"
-    ));
-
     Ok(())
 }
@@ -0,0 +1,47 @@
+use crate::error::{Result, UvupError};
+use crate::shell::detect::{ShellType, parse_shell_type};
+use clap_complete::env::Shells;
+use std::env;
+use std::io::{self, Write};
+
+/// Environment variable that switches the binary into completion mode
+pub(crate) const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Print the completion registration script for a shell
+///
+/// The script calls back into this binary at completion time, so values such as
+/// environment names are always completed from the current envs root.
+pub(crate) fn run(shell: &str) -> Result<()> {
+    let shell_type = parse_shell_type(shell)?;
+
+    let shells = Shells::builtins();
+    let completer = completion_shell_name(shell_type)
+        .and_then(|name| shells.completer(name))
+        .ok_or_else(|| UvupError::CompletionsNotSupported(shell_type.name().to_string()))?;
+
+    let exe = env::current_exe()
+        .map_err(|e| UvupError::PathError(format!("Failed to get current executable: {e}")))?;
+
+    let mut script = Vec::new();
+    completer.write_registration(
+        COMPLETE_VAR,
+        "uvup",
+        "uvup",
+        &exe.to_string_lossy(),
+        &mut script,
+    )?;
+
+    io::stdout().write_all(&script)?;
+    Ok(())
+}
+
+/// Map a shell to the name used by `clap_complete`
+fn completion_shell_name(shell: ShellType) -> Option<&'static str> {
+    match shell {
+        ShellType::Bash => Some("bash"),
+        ShellType::Zsh => Some("zsh"),
+        ShellType::Fish => Some("fish"),
+        ShellType::PowerShell => Some("powershell"),
+        ShellType::Nushell | ShellType::Tcsh | ShellType::Xonsh => None,
+    }
+}
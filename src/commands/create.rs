@@ -1,7 +1,9 @@
+use crate::env::journal;
 use crate::env::paths::{get_env_path, get_envs_dir, validate_env_name};
 use crate::error::{Result, UvupError};
 use crate::utils::print_success;
 use std::fs;
+use std::path::Path;
 use std::process::Command;
 
 const DEFAULT_PYTHON_VERSION: &str = "3.12";
@@ -22,8 +24,13 @@ pub(crate) fn run(name: String, python_version: Option<&str>) -> Result<()> {
 
     let py_version = python_version.unwrap_or(DEFAULT_PYTHON_VERSION);
 
+    journal::record(&name, || create_env(&name, &env_path, py_version))
+}
+
+/// Initialize the project and its venv, removing the directory on failure
+fn create_env(name: &str, env_path: &Path, py_version: &str) -> Result<()> {
     // Create project directory
-    fs::create_dir_all(&env_path)?;
+    fs::create_dir_all(env_path)?;
 
     // Initialize uv project
     let init_status = Command::new("uv")
@@ -31,7 +38,7 @@ pub(crate) fn run(name: String, python_version: Option<&str>) -> Result<()> {
         .arg("--no-readme")
         .arg("--python")
         .arg(py_version)
-        .current_dir(&env_path)
+        .current_dir(env_path)
         .status()
         .map_err(|e| {
             UvupError::CommandExecutionFailed(format!("Failed to execute uv init: {e}"))
@@ -39,16 +46,18 @@ pub(crate) fn run(name: String, python_version: Option<&str>) -> Result<()> {
 
     if !init_status.success() {
         // Clean up on failure
-        let _ = fs::remove_dir_all(&env_path);
+        let _ = fs::remove_dir_all(env_path);
         return Err(UvupError::CommandExecutionFailed(format!(
             "Failed to initialize project for environment '{name}'"
         )));
     }
 
-    // Create virtual environment
+    // Create virtual environment, prompting with the env name instead of ".venv"
     let venv_status = Command::new("uv")
         .arg("venv")
-        .current_dir(&env_path)
+        .arg("--prompt")
+        .arg(name)
+        .current_dir(env_path)
         .status()
         .map_err(|e| {
             UvupError::CommandExecutionFailed(format!("Failed to execute uv venv: {e}"))
@@ -59,7 +68,7 @@ pub(crate) fn run(name: String, python_version: Option<&str>) -> Result<()> {
         Ok(())
     } else {
         // Clean up on failure
-        let _ = fs::remove_dir_all(&env_path);
+        let _ = fs::remove_dir_all(env_path);
         Err(UvupError::CommandExecutionFailed(format!(
             "Failed to create environment '{name}'"
         )))
@@ -0,0 +1,62 @@
+use crate::commands::activate_script::hook_script_actions;
+use crate::env::hooks::{self, HookKind};
+use crate::env::paths::env_name_for_venv;
+use crate::env::stack::{ENV_STACK_VAR, read_stack, stack_value};
+use crate::env::vars;
+use crate::error::{Result, UvupError};
+use crate::shell::detect::{detect_shell, parse_shell_type};
+use crate::shell::script::{Action, render, venv_activate_script};
+use std::env;
+use std::io::{self, Write};
+
+/// Print shell code that deactivates the current venv, meant to be evaluated by the shell
+///
+/// Variables set by the environment are restored to their previous values. If
+/// `uvup activate --stack` left previous venvs on the activation stack, the
+/// most recent one that still exists is activated again.
+pub(crate) fn run(shell: Option<&str>) -> Result<()> {
+    let shell_type = match shell {
+        Some(shell_name) => parse_shell_type(shell_name)?,
+        None => detect_shell()?,
+    };
+
+    if env::var_os("VIRTUAL_ENV").is_none() {
+        return Err(UvupError::NoActiveEnvironment);
+    }
+
+    let mut actions = hook_script_actions(HookKind::Deactivate, shell_type)?;
+    actions.extend(vars::restore_actions());
+    actions.push(Action::DeactivateVenv);
+    actions.push(Action::UnsetVar("UVUP_ACTIVE_ENV".to_string()));
+
+    let mut entries = read_stack();
+    while let Some(previous_venv) = entries.pop() {
+        // Skip venvs deleted since they were stacked
+        if !venv_activate_script(shell_type, &previous_venv).exists() {
+            continue;
+        }
+
+        let env_name = env_name_for_venv(&previous_venv);
+        actions.push(Action::ActivateVenv(previous_venv));
+        if let Some(name) = env_name {
+            let env_vars = vars::load(&name)?;
+            let scripts = hooks::shell_scripts(&name, HookKind::Activate, shell_type)?;
+            actions.push(Action::SetVar("UVUP_ACTIVE_ENV".to_string(), name));
+            actions.extend(vars::apply_actions(&env_vars));
+            actions.extend(scripts.into_iter().map(Action::SourceScript));
+        }
+        break;
+    }
+
+    if entries.is_empty() {
+        actions.push(Action::UnsetVar(ENV_STACK_VAR.to_string()));
+    } else {
+        actions.push(Action::SetVar(
+            ENV_STACK_VAR.to_string(),
+            stack_value(&entries)?,
+        ));
+    }
+
+    io::stdout().write_all(render(shell_type, &actions).as_bytes())?;
+    Ok(())
+}
@@ -0,0 +1,343 @@
+use crate::env::lockfile::Lockfile;
+use crate::env::paths::{ensure_env_exists, get_env_path};
+use crate::env::requirement::Requirement;
+use crate::error::{Result, UvupError};
+use crate::utils::print_info;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use toml_edit::DocumentMut;
+
+/// Requirements or versions keyed by normalized package name
+type PackageMap = BTreeMap<String, String>;
+
+/// What `diff` compares for one environment
+#[derive(Debug)]
+struct EnvSpec {
+    python: Option<String>,
+    dependencies: PackageMap,
+    optional_dependencies: BTreeMap<String, PackageMap>,
+    dependency_groups: BTreeMap<String, PackageMap>,
+    /// Resolved versions from uv.lock, `None` without a lock file
+    packages: Option<PackageMap>,
+}
+
+impl EnvSpec {
+    fn load(name: &str) -> Result<Self> {
+        let env_path = get_env_path(name)?;
+        let pyproject = fs::read_to_string(env_path.join("pyproject.toml")).map_err(|e| {
+            UvupError::PathError(format!("Failed to read pyproject.toml of '{name}': {e}"))
+        })?;
+        let lock = fs::read_to_string(env_path.join("uv.lock")).ok();
+        Self::parse(&pyproject, lock.as_deref())
+    }
+
+    fn parse(pyproject: &str, lock: Option<&str>) -> Result<Self> {
+        let doc: DocumentMut = pyproject
+            .parse()
+            .map_err(|e| UvupError::PathError(format!("Failed to parse pyproject.toml: {e}")))?;
+        let project = doc.get("project");
+
+        let groups = |item: Option<&toml_edit::Item>| -> BTreeMap<String, PackageMap> {
+            item.and_then(|i| i.as_table_like())
+                .into_iter()
+                .flat_map(|table| table.iter())
+                .filter_map(|(group, deps)| {
+                    Some((group.to_string(), requirement_map(deps.as_array()?)))
+                })
+                .collect()
+        };
+        Ok(Self {
+            python: project
+                .and_then(|p| p.get("requires-python"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            dependencies: project
+                .and_then(|p| p.get("dependencies"))
+                .and_then(|d| d.as_array())
+                .map(requirement_map)
+                .unwrap_or_default(),
+            optional_dependencies: groups(project.and_then(|p| p.get("optional-dependencies"))),
+            dependency_groups: groups(doc.get("dependency-groups")),
+            packages: lock.map(locked_versions).transpose()?,
+        })
+    }
+}
+
+/// Added, removed and changed entries between two package maps
+#[derive(Debug, Default, PartialEq, Eq)]
+struct MapDiff {
+    added: PackageMap,
+    removed: PackageMap,
+    changed: BTreeMap<String, (String, String)>,
+}
+
+impl MapDiff {
+    fn new(a: &PackageMap, b: &PackageMap) -> Self {
+        let mut diff = Self::default();
+        for (name, value) in a {
+            match b.get(name) {
+                None => {
+                    diff.removed.insert(name.clone(), value.clone());
+                }
+                Some(other) if other != value => {
+                    diff.changed
+                        .insert(name.clone(), (value.clone(), other.clone()));
+                }
+                Some(_) => {}
+            }
+        }
+        for (name, value) in b {
+            if !a.contains_key(name) {
+                diff.added.insert(name.clone(), value.clone());
+            }
+        }
+        diff
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    fn to_json(&self) -> Value {
+        let changed: Map<String, Value> = self
+            .changed
+            .iter()
+            .map(|(name, (a, b))| (name.clone(), json!({ "a": a, "b": b })))
+            .collect();
+        json!({ "added": self.added, "removed": self.removed, "changed": changed })
+    }
+
+    fn write_lines(&self, out: &mut impl Write, show: fn(&str, &str) -> String) -> io::Result<()> {
+        for (name, value) in &self.added {
+            writeln!(out, "  + {}", show(name, value))?;
+        }
+        for (name, value) in &self.removed {
+            writeln!(out, "  - {}", show(name, value))?;
+        }
+        for (name, (a, b)) in &self.changed {
+            writeln!(out, "  ~ {} -> {}", show(name, a), show(name, b))?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare the dependencies and resolved packages of two environments
+pub(crate) fn run(env_a: &str, env_b: &str, json: bool) -> Result<()> {
+    ensure_env_exists(env_a)?;
+    ensure_env_exists(env_b)?;
+
+    let a = EnvSpec::load(env_a)?;
+    let b = EnvSpec::load(env_b)?;
+
+    let dependencies = MapDiff::new(&a.dependencies, &b.dependencies);
+    let optional = diff_groups(&a.optional_dependencies, &b.optional_dependencies);
+    let groups = diff_groups(&a.dependency_groups, &b.dependency_groups);
+    let packages = MapDiff::new(
+        a.packages.as_ref().unwrap_or(&PackageMap::new()),
+        b.packages.as_ref().unwrap_or(&PackageMap::new()),
+    );
+
+    let mut stdout = io::stdout();
+
+    if json {
+        let group_json = |diffs: &BTreeMap<String, MapDiff>| -> Map<String, Value> {
+            diffs
+                .iter()
+                .map(|(group, diff)| (group.clone(), diff.to_json()))
+                .collect()
+        };
+        let report = json!({
+            "a": env_a,
+            "b": env_b,
+            "python": if a.python == b.python {
+                Value::Null
+            } else {
+                json!({ "a": a.python, "b": b.python })
+            },
+            "dependencies": dependencies.to_json(),
+            "optional_dependencies": group_json(&optional),
+            "dependency_groups": group_json(&groups),
+            "packages": packages.to_json(),
+            "locked": { "a": a.packages.is_some(), "b": b.packages.is_some() },
+        });
+        writeln!(stdout, "{report:#}")?;
+        return Ok(());
+    }
+
+    for (name, spec) in [(env_a, &a), (env_b, &b)] {
+        if spec.packages.is_none() {
+            print_info(&format!("Note: '{name}' has no uv.lock"));
+        }
+    }
+
+    if a.python == b.python
+        && dependencies.is_empty()
+        && optional.is_empty()
+        && groups.is_empty()
+        && packages.is_empty()
+    {
+        print_info(&format!("No differences between '{env_a}' and '{env_b}'"));
+        return Ok(());
+    }
+
+    writeln!(stdout, "Comparing '{env_a}' -> '{env_b}'")?;
+
+    if a.python != b.python {
+        let show = |python: Option<&String>| python.map_or("(unset)", String::as_str).to_string();
+        writeln!(
+            stdout,
+            "\nPython: {} -> {}",
+            show(a.python.as_ref()),
+            show(b.python.as_ref())
+        )?;
+    }
+
+    let requirement = |_: &str, requirement: &str| requirement.to_string();
+    if !dependencies.is_empty() {
+        writeln!(stdout, "\nDirect dependencies:")?;
+        dependencies.write_lines(&mut stdout, requirement)?;
+    }
+    for (title, diffs) in [("Optional group", &optional), ("Dependency group", &groups)] {
+        for (group, diff) in diffs {
+            writeln!(stdout, "\n{title} '{group}':")?;
+            diff.write_lines(&mut stdout, requirement)?;
+        }
+    }
+    if !packages.is_empty() {
+        writeln!(stdout, "\nResolved packages (uv.lock):")?;
+        packages.write_lines(&mut stdout, |name, version| format!("{name} {version}"))?;
+    }
+
+    Ok(())
+}
+
+/// Diff each group present in either environment, keeping groups that differ
+fn diff_groups(
+    a: &BTreeMap<String, PackageMap>,
+    b: &BTreeMap<String, PackageMap>,
+) -> BTreeMap<String, MapDiff> {
+    let empty = PackageMap::new();
+    a.keys()
+        .chain(b.keys())
+        .map(|group| {
+            let diff = MapDiff::new(
+                a.get(group).unwrap_or(&empty),
+                b.get(group).unwrap_or(&empty),
+            );
+            (group.clone(), diff)
+        })
+        .filter(|(_, diff)| !diff.is_empty())
+        .collect()
+}
+
+/// Map requirement strings by the normalized name of their package
+fn requirement_map(array: &toml_edit::Array) -> PackageMap {
+    array
+        .iter()
+        .filter_map(|value| value.as_str())
+        .map(|requirement| {
+            (
+                Requirement::parse(requirement).name,
+                requirement.to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Resolved versions from uv.lock, leaving out the project itself
+fn locked_versions(lock: &str) -> Result<PackageMap> {
+    Ok(Lockfile::parse(lock)?
+        .dependencies()
+        .map(|package| (package.name.clone(), package.version_or_dash().to_string()))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PYPROJECT_A: &str = r#"
+[project]
+name = "a"
+requires-python = ">=3.11"
+dependencies = ["requests>=2.28", "numpy"]
+
+[project.optional-dependencies]
+dev = ["pytest"]
+"#;
+
+    const LOCK_A: &str = r#"
+[[package]]
+name = "a"
+version = "0.1.0"
+source = { virtual = "." }
+
+[[package]]
+name = "numpy"
+version = "1.26.4"
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+"#;
+
+    const PYPROJECT_B: &str = r#"
+[project]
+name = "b"
+requires-python = ">=3.11"
+dependencies = ["requests>=2.32", "pandas"]
+
+[dependency-groups]
+lint = ["ruff"]
+"#;
+
+    const LOCK_B: &str = r#"
+[[package]]
+name = "b"
+version = "0.1.0"
+source = { virtual = "." }
+
+[[package]]
+name = "requests"
+version = "2.32.3"
+
+[[package]]
+name = "pandas"
+version = "2.2.2"
+"#;
+
+    #[test]
+    fn test_diff_specs() {
+        let a = EnvSpec::parse(PYPROJECT_A, Some(LOCK_A)).unwrap();
+        let b = EnvSpec::parse(PYPROJECT_B, Some(LOCK_B)).unwrap();
+
+        assert_eq!(a.python, b.python);
+
+        let dependencies = MapDiff::new(&a.dependencies, &b.dependencies);
+        assert_eq!(dependencies.added.keys().collect::<Vec<_>>(), ["pandas"]);
+        assert_eq!(dependencies.removed.keys().collect::<Vec<_>>(), ["numpy"]);
+        assert_eq!(
+            dependencies.changed["requests"],
+            ("requests>=2.28".to_string(), "requests>=2.32".to_string())
+        );
+
+        let optional = diff_groups(&a.optional_dependencies, &b.optional_dependencies);
+        assert_eq!(
+            optional["dev"].removed.keys().collect::<Vec<_>>(),
+            ["pytest"]
+        );
+        let groups = diff_groups(&a.dependency_groups, &b.dependency_groups);
+        assert_eq!(groups["lint"].added.keys().collect::<Vec<_>>(), ["ruff"]);
+
+        // The projects themselves are not compared
+        let packages = MapDiff::new(a.packages.as_ref().unwrap(), b.packages.as_ref().unwrap());
+        assert_eq!(packages.added.keys().collect::<Vec<_>>(), ["pandas"]);
+        assert_eq!(packages.removed.keys().collect::<Vec<_>>(), ["numpy"]);
+        assert_eq!(
+            packages.changed["requests"],
+            ("2.31.0".to_string(), "2.32.3".to_string())
+        );
+    }
+}
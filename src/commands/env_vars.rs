@@ -0,0 +1,60 @@
+use crate::env::paths::ensure_env_exists;
+use crate::env::vars;
+use crate::error::Result;
+use crate::utils::{print_info, print_success};
+use std::io::{self, Write};
+
+/// Set variables exported whenever an environment is active
+pub(crate) fn set(name: &str, assignments: &[String]) -> Result<()> {
+    let parsed = assignments
+        .iter()
+        .map(|assignment| vars::parse_assignment(assignment))
+        .collect::<Result<Vec<_>>>()?;
+
+    ensure_env_exists(name)?;
+
+    let mut env_vars = vars::load(name)?;
+    for (key, value) in parsed {
+        print_success(&format!("Set {key} for '{name}'"));
+        env_vars.insert(key, value);
+    }
+    vars::save(name, &env_vars)?;
+
+    print_info("Reactivate the environment to apply the changes");
+    Ok(())
+}
+
+/// Remove variables from an environment
+pub(crate) fn unset(name: &str, keys: &[String]) -> Result<()> {
+    ensure_env_exists(name)?;
+
+    let mut env_vars = vars::load(name)?;
+    for key in keys {
+        if env_vars.remove(key).is_some() {
+            print_success(&format!("Unset {key} for '{name}'"));
+        } else {
+            print_info(&format!("{key} is not set for '{name}' (skipped)"));
+        }
+    }
+    vars::save(name, &env_vars)?;
+
+    Ok(())
+}
+
+/// List the variables of an environment as `KEY=VALUE` lines
+pub(crate) fn list(name: &str) -> Result<()> {
+    ensure_env_exists(name)?;
+
+    let env_vars = vars::load(name)?;
+    if env_vars.is_empty() {
+        print_info(&format!("No environment variables set for '{name}'"));
+        return Ok(());
+    }
+
+    let mut stdout = io::stdout();
+    for (key, value) in env_vars {
+        writeln!(stdout, "{key}={value}")?;
+    }
+
+    Ok(())
+}
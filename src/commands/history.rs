@@ -0,0 +1,101 @@
+use crate::env::journal::{self, Entry};
+use crate::env::paths::resolve_env;
+use crate::error::Result;
+use crate::utils::print_info;
+use std::io::{self, Write};
+
+/// Number of hash characters shown in the readable history
+const SHORT_HASH_LEN: usize = 12;
+
+/// Show the journal of an environment, defaulting to the active one
+pub(crate) fn run(name: Option<String>, json: bool) -> Result<()> {
+    let name = resolve_env(name)?;
+
+    let entries = journal::read(&name)?;
+    let mut stdout = io::stdout();
+
+    if json {
+        for entry in &entries {
+            writeln!(stdout, "{}", entry.to_json())?;
+        }
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        print_info(&format!("No history for '{name}'"));
+        return Ok(());
+    }
+
+    for entry in &entries {
+        write_entry(&mut stdout, entry)?;
+    }
+
+    Ok(())
+}
+
+fn write_entry(out: &mut impl Write, entry: &Entry) -> io::Result<()> {
+    let outcome = if entry.succeeded() {
+        "success"
+    } else {
+        "failure"
+    };
+    writeln!(
+        out,
+        "{}  {outcome:<7}  {}  {}",
+        entry.timestamp,
+        who(entry),
+        entry.command
+    )?;
+
+    if let Some(error) = &entry.error {
+        writeln!(out, "    {error}")?;
+    }
+    writeln!(
+        out,
+        "    pyproject.toml  {}",
+        describe_change(
+            entry.before.pyproject.as_deref(),
+            entry.after.pyproject.as_deref()
+        )
+    )?;
+    writeln!(
+        out,
+        "    uv.lock         {}",
+        describe_change(entry.before.lock.as_deref(), entry.after.lock.as_deref())
+    )
+}
+
+/// `user@host`, with `?` for whichever part was not recorded
+fn who(entry: &Entry) -> String {
+    format!(
+        "{}@{}",
+        entry.user.as_deref().unwrap_or("?"),
+        entry.host.as_deref().unwrap_or("?")
+    )
+}
+
+fn describe_change(before: Option<&str>, after: Option<&str>) -> String {
+    if before == after {
+        return "unchanged".to_string();
+    }
+    format!("{} -> {}", short_hash(before), short_hash(after))
+}
+
+fn short_hash(hash: Option<&str>) -> &str {
+    hash.map_or("(none)", |hash| hash.get(..SHORT_HASH_LEN).unwrap_or(hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_change() {
+        assert_eq!(describe_change(Some("abc"), Some("abc")), "unchanged");
+        assert_eq!(describe_change(None, None), "unchanged");
+        assert_eq!(
+            describe_change(None, Some("0123456789abcdef")),
+            "(none) -> 0123456789ab"
+        );
+    }
+}
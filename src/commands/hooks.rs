@@ -0,0 +1,97 @@
+use crate::env::hooks::{self, HookKind};
+use crate::env::paths::ensure_env_exists;
+use crate::error::{Result, UvupError};
+use crate::shell::detect::{detect_shell, parse_shell_type};
+use crate::utils::{print_info, print_success};
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process::Command;
+
+/// List the activation and deactivation scripts of an environment
+pub(crate) fn list(name: &str) -> Result<()> {
+    ensure_env_exists(name)?;
+
+    let mut stdout = io::stdout();
+    let mut found = false;
+
+    for kind in [HookKind::Activate, HookKind::Deactivate] {
+        for script in hooks::list_scripts(name, kind)? {
+            writeln!(stdout, "{:<10}  {}", kind.name(), script.display())?;
+            found = true;
+        }
+    }
+
+    if !found {
+        print_info(&format!("No hook scripts for '{name}'"));
+    }
+
+    Ok(())
+}
+
+/// Open a hook script of an environment in the user's editor, creating it if needed
+pub(crate) fn edit(
+    name: &str,
+    kind: HookKind,
+    shell: Option<&str>,
+    script_name: &str,
+) -> Result<()> {
+    ensure_env_exists(name)?;
+    hooks::validate_script_name(script_name)?;
+
+    let shell_type = match shell {
+        Some(shell_name) => parse_shell_type(shell_name)?,
+        None => detect_shell()?,
+    };
+    let extension = hooks::script_extension(shell_type).ok_or_else(|| {
+        UvupError::CommandExecutionFailed(format!(
+            "Hook scripts are not supported for {}",
+            shell_type.name()
+        ))
+    })?;
+
+    let dir = hooks::hook_dir(name, kind)?;
+    fs::create_dir_all(&dir)?;
+
+    let script = dir.join(format!("{script_name}.{extension}"));
+    if !script.exists() {
+        let when = match kind {
+            HookKind::Activate => "after activating",
+            HookKind::Deactivate => "before deactivating",
+        };
+        fs::write(&script, format!("# Sourced by uvup {when} '{name}'\n"))?;
+        print_success(&format!("Created {}", script.display()));
+    }
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| default_editor().to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or_default();
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(&script)
+        .status()
+        .map_err(|e| {
+            UvupError::CommandExecutionFailed(format!("Failed to run editor '{program}': {e}"))
+        })?;
+
+    if !status.success() {
+        return Err(UvupError::CommandExecutionFailed(format!(
+            "Editor '{program}' exited with {status}"
+        )));
+    }
+
+    Ok(())
+}
+
+fn default_editor() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "notepad"
+    } else {
+        "vi"
+    }
+}
@@ -1,27 +1,56 @@
 // Allow println! in this file as it's used for user-facing output
 #![allow(clippy::print_stdout)]
 
+use crate::env::paths::get_shell_dir;
 use crate::error::{Result, UvupError};
-use crate::shell::detect::{ShellType, detect_shell};
-use crate::shell::{bash, fish, powershell};
+use crate::shell::detect::{ShellType, detect_shell, parse_shell_type};
+use crate::shell::{bash, fish, nushell, powershell, rc_block, tcsh, xonsh};
+use crate::utils::compact_timestamp;
 use std::env;
 use std::fs;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[cfg(not(target_os = "windows"))]
 use std::collections::HashSet;
 
-const INIT_MARKER_START: &str = "# uvup initialization";
+const ALL_SHELLS: [ShellType; 7] = [
+    ShellType::Bash,
+    ShellType::Zsh,
+    ShellType::Fish,
+    ShellType::PowerShell,
+    ShellType::Nushell,
+    ShellType::Tcsh,
+    ShellType::Xonsh,
+];
 
 /// Initialize or uninitialize shell hooks for uvup
-pub(crate) fn run(shell: Option<String>, raw: bool, reverse: bool, dry_run: bool) -> Result<()> {
+#[allow(clippy::fn_params_excessive_bools)]
+pub(crate) fn run(
+    shell: Option<&str>,
+    raw: bool,
+    reverse: bool,
+    dry_run: bool,
+    completions: bool,
+    status: bool,
+    refresh_hooks: bool,
+) -> Result<()> {
+    if refresh_hooks {
+        return refresh_hook_files();
+    }
+
+    let requested_shell = shell.map(parse_shell_type).transpose()?;
+
     if raw {
-        return print_current_shell_script();
+        return print_shell_script(requested_shell);
+    }
+
+    if status {
+        return print_status(requested_shell);
     }
 
-    let shells_to_init = if let Some(shell_name) = shell {
-        vec![parse_shell_type(&shell_name)?]
+    let shells_to_init = if let Some(shell_type) = requested_shell {
+        vec![shell_type]
     } else {
         detect_available_shells()
     };
@@ -30,36 +59,23 @@ pub(crate) fn run(shell: Option<String>, raw: bool, reverse: bool, dry_run: bool
         if reverse {
             uninitialize_shell(shell_type, dry_run)?;
         } else {
-            initialize_shell(shell_type, dry_run)?;
+            initialize_shell(shell_type, dry_run, completions)?;
         }
     }
 
     Ok(())
 }
 
-/// Print shell hook script for the current shell
-fn print_current_shell_script() -> Result<()> {
-    let shell = detect_shell()?;
-    let hook_script = match shell {
-        ShellType::Bash | ShellType::Zsh => bash::BASH_HOOK,
-        ShellType::Fish => fish::FISH_HOOK,
-        ShellType::PowerShell => powershell::POWERSHELL_HOOK,
+/// Print the shell hook script for the given shell, or the current one
+fn print_shell_script(shell: Option<ShellType>) -> Result<()> {
+    let shell = match shell {
+        Some(shell) => shell,
+        None => detect_shell()?,
     };
-    io::stdout().write_all(hook_script.as_bytes())?;
+    io::stdout().write_all(hook_script(shell).as_bytes())?;
     Ok(())
 }
 
-/// Parse shell type from string name
-fn parse_shell_type(name: &str) -> Result<ShellType> {
-    match name.to_lowercase().as_str() {
-        "powershell" | "pwsh" | "ps" => Ok(ShellType::PowerShell),
-        "bash" | "git-bash" => Ok(ShellType::Bash),
-        "zsh" => Ok(ShellType::Zsh),
-        "fish" => Ok(ShellType::Fish),
-        _ => Err(UvupError::PathError(format!("Unknown shell: {name}"))),
-    }
-}
-
 /// Detect available shells on the system
 fn detect_available_shells() -> Vec<ShellType> {
     let mut shells = Vec::new();
@@ -71,6 +87,10 @@ fn detect_available_shells() -> Vec<ShellType> {
         if is_git_bash_installed() {
             shells.push(ShellType::Bash);
         }
+
+        if is_nushell_installed() {
+            shells.push(ShellType::Nushell);
+        }
     }
 
     #[cfg(not(target_os = "windows"))]
@@ -89,6 +109,18 @@ fn detect_available_shells() -> Vec<ShellType> {
             detected.insert(ShellType::Fish);
         }
 
+        if is_nushell_installed() {
+            detected.insert(ShellType::Nushell);
+        }
+
+        if is_shell_available("tcsh") {
+            detected.insert(ShellType::Tcsh);
+        }
+
+        if is_shell_available("xonsh") {
+            detected.insert(ShellType::Xonsh);
+        }
+
         shells.extend(detected);
     }
 
@@ -111,32 +143,95 @@ fn is_shell_available(shell_name: &str) -> bool {
     std::process::Command::new("which")
         .arg(shell_name)
         .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Check if Nushell (nu) is installed
+fn is_nushell_installed() -> bool {
+    std::process::Command::new("nu")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success())
 }
 
-fn initialize_shell(shell: ShellType, dry_run: bool) -> Result<()> {
+fn initialize_shell(shell: ShellType, dry_run: bool, completions: bool) -> Result<()> {
+    let completions_line = if completions || has_completions_line(shell) {
+        completions_init_line(shell)
+    } else {
+        None
+    };
+
+    if completions && completions_line.is_none() {
+        println!(
+            "Completions are not available for {} (skipped)",
+            shell.name()
+        );
+    }
+
     match shell {
-        ShellType::PowerShell => initialize_powershell(dry_run),
-        ShellType::Bash => initialize_bash(dry_run),
-        ShellType::Zsh => initialize_zsh(dry_run),
-        ShellType::Fish => initialize_fish_impl(dry_run),
+        ShellType::PowerShell => initialize_powershell(dry_run, completions_line),
+        ShellType::Bash => initialize_bash(dry_run, completions_line),
+        ShellType::Zsh => initialize_zsh(dry_run, completions_line),
+        ShellType::Fish => initialize_fish_impl(dry_run, completions_line),
+        ShellType::Nushell => initialize_hook_file_shell(&nushell_hook_file_shell()?, dry_run),
+        ShellType::Tcsh => initialize_hook_file_shell(&tcsh_hook_file_shell()?, dry_run),
+        ShellType::Xonsh => initialize_hook_file_shell(&xonsh_hook_file_shell()?, dry_run),
     }
 }
 
+/// Get the config line that registers completions for a shell, if supported
+fn completions_init_line(shell: ShellType) -> Option<&'static str> {
+    match shell {
+        ShellType::PowerShell => {
+            Some("(& uvup.exe completions powershell) -join \"`n\" | Invoke-Expression")
+        }
+        ShellType::Bash => Some("eval \"$(command uvup completions bash)\""),
+        ShellType::Zsh => Some("eval \"$(command uvup completions zsh)\""),
+        ShellType::Fish => Some("command uvup completions fish | source"),
+        ShellType::Nushell | ShellType::Tcsh | ShellType::Xonsh => None,
+    }
+}
+
+/// Check whether the existing uvup block already registers completions
+fn has_completions_line(shell: ShellType) -> bool {
+    let Some(line) = completions_init_line(shell) else {
+        return false;
+    };
+    read_shell_status(shell).is_ok_and(|status| {
+        status
+            .blocks
+            .iter()
+            .any(|block| block.lines.iter().any(|l| l == line))
+    })
+}
+
+/// Build the lines of the uvup block in a shell config file
+fn init_block<'a>(hook_line: &'a str, completions_line: Option<&'a str>) -> Vec<&'a str> {
+    let mut block = vec![hook_line];
+    block.extend(completions_line);
+    block
+}
+
 fn uninitialize_shell(shell: ShellType, dry_run: bool) -> Result<()> {
     match shell {
         ShellType::PowerShell => uninitialize_powershell(dry_run),
         ShellType::Bash => uninitialize_bash(dry_run),
         ShellType::Zsh => uninitialize_zsh(dry_run),
         ShellType::Fish => uninitialize_fish_impl(dry_run),
+        ShellType::Nushell => uninitialize_hook_file_shell(&nushell_hook_file_shell()?, dry_run),
+        ShellType::Tcsh => uninitialize_hook_file_shell(&tcsh_hook_file_shell()?, dry_run),
+        ShellType::Xonsh => uninitialize_hook_file_shell(&xonsh_hook_file_shell()?, dry_run),
     }
 }
 
-fn initialize_powershell(dry_run: bool) -> Result<()> {
+fn initialize_powershell(dry_run: bool, completions_line: Option<&str>) -> Result<()> {
     let profile_path = get_powershell_profile()?;
 
     if dry_run {
+        println!(
+            "Would write: {}",
+            hook_file_path(ShellType::PowerShell)?.display()
+        );
         println!("Would modify: {}", profile_path.display());
         println!("  Add uvup initialization to PowerShell profile");
         return Ok(());
@@ -146,25 +241,29 @@ fn initialize_powershell(dry_run: bool) -> Result<()> {
         fs::create_dir_all(parent)?;
     }
 
+    let source_line = install_hook_file(ShellType::PowerShell)?;
+
     let content = if profile_path.exists() {
         fs::read_to_string(&profile_path)?
     } else {
         String::new()
     };
 
-    if content.contains(INIT_MARKER_START) {
-        println!("PowerShell already initialized (skipped)");
+    let new_content = rc_block::upsert_block(&content, &init_block(&source_line, completions_line));
+
+    if new_content == content {
+        println!("PowerShell already up to date (skipped)");
         return Ok(());
     }
 
-    let init_code =
-        format!("\n{INIT_MARKER_START}\nInvoke-Expression ((uvup init --raw) -join \"`n\")\n");
-
-    let new_content = content + &init_code;
-    fs::write(&profile_path, new_content)?;
+    write_config_file(&profile_path, &new_content)?;
 
     println!("✓ PowerShell initialized");
     println!("  Profile: {}", profile_path.display());
+    println!(
+        "  Hook: {}",
+        hook_file_path(ShellType::PowerShell)?.display()
+    );
     println!("  Please restart your PowerShell session");
 
     Ok(())
@@ -185,14 +284,15 @@ fn uninitialize_powershell(dry_run: bool) -> Result<()> {
     }
 
     let content = fs::read_to_string(&profile_path)?;
-    let new_content = remove_init_block(&content);
+    let new_content = rc_block::remove_blocks(&content);
 
     if new_content == content {
         println!("PowerShell not initialized (skipped)");
         return Ok(());
     }
 
-    fs::write(&profile_path, new_content)?;
+    write_config_file(&profile_path, &new_content)?;
+    remove_hook_file(ShellType::PowerShell);
     println!("✓ Removed uvup from PowerShell");
 
     Ok(())
@@ -237,20 +337,20 @@ fn is_pwsh_installed() -> bool {
         .is_ok()
 }
 
-fn initialize_bash(dry_run: bool) -> Result<()> {
+fn initialize_bash(dry_run: bool, completions_line: Option<&str>) -> Result<()> {
     #[cfg(target_os = "windows")]
     {
-        initialize_bash_windows(dry_run)
+        initialize_bash_windows(dry_run, completions_line)
     }
 
     #[cfg(not(target_os = "windows"))]
     {
-        initialize_bash_unix(dry_run)
+        initialize_bash_unix(dry_run, completions_line)
     }
 }
 
 #[cfg(target_os = "windows")]
-fn initialize_bash_windows(dry_run: bool) -> Result<()> {
+fn initialize_bash_windows(dry_run: bool, completions_line: Option<&str>) -> Result<()> {
     let home = env::var("HOME")
         .or_else(|_| env::var("USERPROFILE"))
         .map_err(|_| UvupError::PathError("HOME/USERPROFILE not set".to_string()))?;
@@ -260,6 +360,10 @@ fn initialize_bash_windows(dry_run: bool) -> Result<()> {
     let bash_profile_path = home_path.join(".bash_profile");
 
     if dry_run {
+        println!(
+            "Would write: {}",
+            hook_file_path(ShellType::Bash)?.display()
+        );
         if !bash_profile_path.exists() {
             println!("Would create: {}", bash_profile_path.display());
             println!("  Add .bashrc sourcing logic");
@@ -293,42 +397,49 @@ fi
 "
             .to_string()
         };
-        fs::write(&bash_profile_path, bash_profile_content)?;
+        write_config_file(&bash_profile_path, &bash_profile_content)?;
         println!("✓ Created/Updated .bash_profile to source .bashrc");
     }
 
+    let source_line = install_hook_file(ShellType::Bash)?;
+
     let bashrc_content = if bashrc_path.exists() {
         fs::read_to_string(&bashrc_path)?
     } else {
         String::new()
     };
 
-    if bashrc_content.contains(INIT_MARKER_START) {
-        println!("Bash already initialized (skipped)");
+    let new_content =
+        rc_block::upsert_block(&bashrc_content, &init_block(&source_line, completions_line));
+
+    if new_content == bashrc_content {
+        println!("Bash already up to date (skipped)");
         return Ok(());
     }
 
-    let init_code = format!("\n{INIT_MARKER_START}\neval \"$(uvup init --raw)\"\n");
-
-    let new_content = bashrc_content + &init_code;
-    fs::write(&bashrc_path, new_content)?;
+    write_config_file(&bashrc_path, &new_content)?;
 
     println!("✓ Bash initialized");
     println!("  Profile: {}", bash_profile_path.display());
     println!("  Config: {}", bashrc_path.display());
+    println!("  Hook: {}", hook_file_path(ShellType::Bash)?.display());
     println!("  Please restart your Bash session or run: source ~/.bash_profile");
 
     Ok(())
 }
 
 #[cfg(not(target_os = "windows"))]
-fn initialize_bash_unix(dry_run: bool) -> Result<()> {
+fn initialize_bash_unix(dry_run: bool, completions_line: Option<&str>) -> Result<()> {
     let home = env::var("HOME").map_err(|_| UvupError::PathError("HOME not set".to_string()))?;
     let home_path = PathBuf::from(home);
     let bashrc_path = home_path.join(".bashrc");
     let bash_profile_path = home_path.join(".bash_profile");
 
     if dry_run {
+        println!(
+            "Would write: {}",
+            hook_file_path(ShellType::Bash)?.display()
+        );
         if !bash_profile_path.exists() {
             println!("Would create: {}", bash_profile_path.display());
             println!("  Add .bashrc sourcing logic");
@@ -362,29 +473,32 @@ fi
 "
             .to_string()
         };
-        fs::write(&bash_profile_path, bash_profile_content)?;
+        write_config_file(&bash_profile_path, &bash_profile_content)?;
         println!("✓ Created/Updated .bash_profile to source .bashrc");
     }
 
+    let source_line = install_hook_file(ShellType::Bash)?;
+
     let bashrc_content = if bashrc_path.exists() {
         fs::read_to_string(&bashrc_path)?
     } else {
         String::new()
     };
 
-    if bashrc_content.contains(INIT_MARKER_START) {
-        println!("Bash already initialized (skipped)");
+    let new_content =
+        rc_block::upsert_block(&bashrc_content, &init_block(&source_line, completions_line));
+
+    if new_content == bashrc_content {
+        println!("Bash already up to date (skipped)");
         return Ok(());
     }
 
-    let init_code = format!("\n{INIT_MARKER_START}\neval \"$(uvup init --raw)\"\n");
-
-    let new_content = bashrc_content + &init_code;
-    fs::write(&bashrc_path, new_content)?;
+    write_config_file(&bashrc_path, &new_content)?;
 
     println!("✓ Bash initialized");
     println!("  Profile: {}", bash_profile_path.display());
     println!("  Config: {}", bashrc_path.display());
+    println!("  Hook: {}", hook_file_path(ShellType::Bash)?.display());
     println!("  Please restart your Bash session or run: source ~/.bash_profile");
 
     Ok(())
@@ -422,14 +536,15 @@ fn uninitialize_bash_windows(dry_run: bool) -> Result<()> {
     }
 
     let content = fs::read_to_string(&bashrc_path)?;
-    let new_content = remove_init_block(&content);
+    let new_content = rc_block::remove_blocks(&content);
 
     if new_content == content {
         println!("Bash not initialized (skipped)");
         return Ok(());
     }
 
-    fs::write(&bashrc_path, new_content)?;
+    write_config_file(&bashrc_path, &new_content)?;
+    remove_hook_file(ShellType::Bash);
     println!("✓ Removed uvup from Bash");
 
     Ok(())
@@ -452,40 +567,38 @@ fn uninitialize_bash_unix(dry_run: bool) -> Result<()> {
     }
 
     let content = fs::read_to_string(&bashrc_path)?;
-    let new_content = remove_init_block(&content);
+    let new_content = rc_block::remove_blocks(&content);
 
     if new_content == content {
         println!("Bash not initialized (skipped)");
         return Ok(());
     }
 
-    fs::write(&bashrc_path, new_content)?;
+    write_config_file(&bashrc_path, &new_content)?;
+    remove_hook_file(ShellType::Bash);
     println!("✓ Removed uvup from Bash");
 
     Ok(())
 }
 
-fn initialize_zsh(dry_run: bool) -> Result<()> {
+fn initialize_zsh(dry_run: bool, completions_line: Option<&str>) -> Result<()> {
     #[cfg(target_os = "windows")]
     {
-        initialize_zsh_windows(dry_run)
+        initialize_zsh_windows(dry_run, completions_line)
     }
 
     #[cfg(not(target_os = "windows"))]
     {
-        initialize_zsh_unix(dry_run)
+        initialize_zsh_unix(dry_run, completions_line)
     }
 }
 
 #[cfg(target_os = "windows")]
-fn initialize_zsh_windows(dry_run: bool) -> Result<()> {
-    let home = env::var("HOME")
-        .or_else(|_| env::var("USERPROFILE"))
-        .map_err(|_| UvupError::PathError("HOME/USERPROFILE not set".to_string()))?;
-
-    let zshrc_path = PathBuf::from(home).join(".zshrc");
+fn initialize_zsh_windows(dry_run: bool, completions_line: Option<&str>) -> Result<()> {
+    let zshrc_path = get_zdotdir()?.join(".zshrc");
 
     if dry_run {
+        println!("Would write: {}", hook_file_path(ShellType::Zsh)?.display());
         if !zshrc_path.exists() {
             println!("Would create: {}", zshrc_path.display());
         }
@@ -494,57 +607,63 @@ fn initialize_zsh_windows(dry_run: bool) -> Result<()> {
         return Ok(());
     }
 
+    let source_line = install_hook_file(ShellType::Zsh)?;
+
     let zshrc_content = if zshrc_path.exists() {
         fs::read_to_string(&zshrc_path)?
     } else {
         String::new()
     };
 
-    if zshrc_content.contains(INIT_MARKER_START) {
-        println!("Zsh already initialized (skipped)");
+    let new_content =
+        rc_block::upsert_block(&zshrc_content, &init_block(&source_line, completions_line));
+
+    if new_content == zshrc_content {
+        println!("Zsh already up to date (skipped)");
         return Ok(());
     }
 
-    let init_code = format!("\n{INIT_MARKER_START}\neval \"$(uvup init --raw)\"\n");
-
-    let new_content = zshrc_content + &init_code;
-    fs::write(&zshrc_path, new_content)?;
+    write_config_file(&zshrc_path, &new_content)?;
 
     println!("✓ Zsh initialized");
     println!("  Config: {}", zshrc_path.display());
+    println!("  Hook: {}", hook_file_path(ShellType::Zsh)?.display());
     println!("  Please restart your Zsh session or run: source ~/.zshrc");
 
     Ok(())
 }
 
 #[cfg(not(target_os = "windows"))]
-fn initialize_zsh_unix(dry_run: bool) -> Result<()> {
+fn initialize_zsh_unix(dry_run: bool, completions_line: Option<&str>) -> Result<()> {
     let zshrc_path = get_zsh_profile()?;
 
     if dry_run {
+        println!("Would write: {}", hook_file_path(ShellType::Zsh)?.display());
         println!("Would modify: {}", zshrc_path.display());
         println!("  Add uvup initialization to Zsh profile");
         return Ok(());
     }
 
+    let source_line = install_hook_file(ShellType::Zsh)?;
+
     let content = if zshrc_path.exists() {
         fs::read_to_string(&zshrc_path)?
     } else {
         String::new()
     };
 
-    if content.contains(INIT_MARKER_START) {
-        println!("Zsh already initialized (skipped)");
+    let new_content = rc_block::upsert_block(&content, &init_block(&source_line, completions_line));
+
+    if new_content == content {
+        println!("Zsh already up to date (skipped)");
         return Ok(());
     }
 
-    let init_code = format!("\n{INIT_MARKER_START}\neval \"$(uvup init --raw)\"\n");
-
-    let new_content = content + &init_code;
-    fs::write(&zshrc_path, new_content)?;
+    write_config_file(&zshrc_path, &new_content)?;
 
     println!("✓ Zsh initialized");
     println!("  Profile: {}", zshrc_path.display());
+    println!("  Hook: {}", hook_file_path(ShellType::Zsh)?.display());
     println!(
         "  Please restart your Zsh session or run: source {}",
         zshrc_path.display()
@@ -567,11 +686,7 @@ fn uninitialize_zsh(dry_run: bool) -> Result<()> {
 
 #[cfg(target_os = "windows")]
 fn uninitialize_zsh_windows(dry_run: bool) -> Result<()> {
-    let home = env::var("HOME")
-        .or_else(|_| env::var("USERPROFILE"))
-        .map_err(|_| UvupError::PathError("HOME/USERPROFILE not set".to_string()))?;
-
-    let zshrc_path = PathBuf::from(home).join(".zshrc");
+    let zshrc_path = get_zdotdir()?.join(".zshrc");
 
     if !zshrc_path.exists() {
         println!("Zsh not initialized (skipped)");
@@ -585,14 +700,15 @@ fn uninitialize_zsh_windows(dry_run: bool) -> Result<()> {
     }
 
     let content = fs::read_to_string(&zshrc_path)?;
-    let new_content = remove_init_block(&content);
+    let new_content = rc_block::remove_blocks(&content);
 
     if new_content == content {
         println!("Zsh not initialized (skipped)");
         return Ok(());
     }
 
-    fs::write(&zshrc_path, new_content)?;
+    write_config_file(&zshrc_path, &new_content)?;
+    remove_hook_file(ShellType::Zsh);
     println!("✓ Removed uvup from Zsh");
 
     Ok(())
@@ -614,14 +730,15 @@ fn uninitialize_zsh_unix(dry_run: bool) -> Result<()> {
     }
 
     let content = fs::read_to_string(&zshrc_path)?;
-    let new_content = remove_init_block(&content);
+    let new_content = rc_block::remove_blocks(&content);
 
     if new_content == content {
         println!("Zsh not initialized (skipped)");
         return Ok(());
     }
 
-    fs::write(&zshrc_path, new_content)?;
+    write_config_file(&zshrc_path, &new_content)?;
+    remove_hook_file(ShellType::Zsh);
     println!("✓ Removed uvup from Zsh");
 
     Ok(())
@@ -630,16 +747,14 @@ fn uninitialize_zsh_unix(dry_run: bool) -> Result<()> {
 /// Get Zsh profile path (Unix-like systems)
 #[cfg(not(target_os = "windows"))]
 fn get_zsh_profile() -> Result<PathBuf> {
-    let home = env::var("HOME").map_err(|_| UvupError::PathError("HOME not set".to_string()))?;
-
-    let home_path = PathBuf::from(home);
+    let zdotdir = get_zdotdir()?;
 
-    let zshrc = home_path.join(".zshrc");
+    let zshrc = zdotdir.join(".zshrc");
     if zshrc.exists() {
         return Ok(zshrc);
     }
 
-    let zprofile = home_path.join(".zprofile");
+    let zprofile = zdotdir.join(".zprofile");
     if zprofile.exists() {
         return Ok(zprofile);
     }
@@ -647,10 +762,14 @@ fn get_zsh_profile() -> Result<PathBuf> {
     Ok(zshrc)
 }
 
-fn initialize_fish_impl(dry_run: bool) -> Result<()> {
+fn initialize_fish_impl(dry_run: bool, completions_line: Option<&str>) -> Result<()> {
     let config_path = get_fish_config()?;
 
     if dry_run {
+        println!(
+            "Would write: {}",
+            hook_file_path(ShellType::Fish)?.display()
+        );
         if !config_path.exists() {
             println!("Would create: {}", config_path.display());
         }
@@ -663,24 +782,26 @@ fn initialize_fish_impl(dry_run: bool) -> Result<()> {
         fs::create_dir_all(parent)?;
     }
 
+    let source_line = install_hook_file(ShellType::Fish)?;
+
     let content = if config_path.exists() {
         fs::read_to_string(&config_path)?
     } else {
         String::new()
     };
 
-    if content.contains(INIT_MARKER_START) {
-        println!("Fish already initialized (skipped)");
+    let new_content = rc_block::upsert_block(&content, &init_block(&source_line, completions_line));
+
+    if new_content == content {
+        println!("Fish already up to date (skipped)");
         return Ok(());
     }
 
-    let init_code = format!("\n{INIT_MARKER_START}\nuvup init --raw | source\n");
-
-    let new_content = content + &init_code;
-    fs::write(&config_path, new_content)?;
+    write_config_file(&config_path, &new_content)?;
 
     println!("✓ Fish initialized");
     println!("  Config: {}", config_path.display());
+    println!("  Hook: {}", hook_file_path(ShellType::Fish)?.display());
     println!(
         "  Please restart your Fish session or run: source {}",
         config_path.display()
@@ -704,53 +825,548 @@ fn uninitialize_fish_impl(dry_run: bool) -> Result<()> {
     }
 
     let content = fs::read_to_string(&config_path)?;
-    let new_content = remove_init_block(&content);
+    let new_content = rc_block::remove_blocks(&content);
 
     if new_content == content {
         println!("Fish not initialized (skipped)");
         return Ok(());
     }
 
-    fs::write(&config_path, new_content)?;
+    write_config_file(&config_path, &new_content)?;
+    remove_hook_file(ShellType::Fish);
     println!("✓ Removed uvup from Fish");
 
     Ok(())
 }
 
+/// Get the directory Zsh reads its startup files from (`$ZDOTDIR`, else home)
+fn get_zdotdir() -> Result<PathBuf> {
+    if let Some(zdotdir) = env::var_os("ZDOTDIR").filter(|d| !d.is_empty()) {
+        return Ok(PathBuf::from(zdotdir));
+    }
+
+    let home = env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .map_err(|_| UvupError::PathError("HOME/USERPROFILE not set".to_string()))?;
+
+    Ok(PathBuf::from(home))
+}
+
+/// Get the XDG config directory (`$XDG_CONFIG_HOME`, else `~/.config`)
+fn get_xdg_config_home() -> Result<PathBuf> {
+    if let Some(config_home) = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|d| d.is_absolute())
+    {
+        return Ok(config_home);
+    }
+
+    let home = env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .map_err(|_| UvupError::PathError("HOME/USERPROFILE not set".to_string()))?;
+
+    Ok(PathBuf::from(home).join(".config"))
+}
+
 /// Get Fish config file path
 fn get_fish_config() -> Result<PathBuf> {
+    Ok(get_xdg_config_home()?.join("fish").join("config.fish"))
+}
+
+/// Get the hook script for a shell
+fn hook_script(shell: ShellType) -> &'static str {
+    match shell {
+        ShellType::Bash | ShellType::Zsh => bash::BASH_HOOK,
+        ShellType::Fish => fish::FISH_HOOK,
+        ShellType::PowerShell => powershell::POWERSHELL_HOOK,
+        ShellType::Nushell => nushell::NUSHELL_HOOK,
+        ShellType::Tcsh => tcsh::TCSH_HOOK,
+        ShellType::Xonsh => xonsh::XONSH_HOOK,
+    }
+}
+
+/// Get the hook file the config of a shell sources (`~/.uvup/.shell/uvup.<ext>`)
+fn hook_file_path(shell: ShellType) -> Result<PathBuf> {
+    let file_name = match shell {
+        ShellType::Bash => "uvup.bash",
+        ShellType::Zsh => "uvup.zsh",
+        ShellType::Fish => "uvup.fish",
+        ShellType::PowerShell => "uvup.ps1",
+        ShellType::Nushell => "uvup.nu",
+        ShellType::Tcsh => "uvup.csh",
+        ShellType::Xonsh => "uvup.xsh",
+    };
+    Ok(get_shell_dir()?.join(file_name))
+}
+
+/// Get the hook file content, stamped with the uvup version that wrote it
+fn hook_file_content(shell: ShellType) -> String {
+    format!(
+        "# Generated by uvup {}, refreshed by 'uvup init' and 'uvup update'\n{}",
+        env!("CARGO_PKG_VERSION"),
+        hook_script(shell)
+    )
+}
+
+/// Get the config line that sources the hook file of a shell
+fn hook_source_line(shell: ShellType) -> Result<String> {
+    let hook_path = hook_file_path(shell)?;
+    Ok(match shell {
+        ShellType::Bash | ShellType::Zsh => bash::source_line(&hook_path),
+        ShellType::Fish => fish::source_line(&hook_path),
+        ShellType::PowerShell => powershell::source_line(&hook_path),
+        ShellType::Nushell => nushell::source_line(&hook_path),
+        ShellType::Tcsh => tcsh::source_line(&hook_path),
+        ShellType::Xonsh => xonsh::source_line(&hook_path),
+    })
+}
+
+/// Write the hook file for a shell and get the config line that sources it
+fn install_hook_file(shell: ShellType) -> Result<String> {
+    let hook_path = hook_file_path(shell)?;
+    if let Some(parent) = hook_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(&hook_path, &hook_file_content(shell))?;
+    hook_source_line(shell)
+}
+
+/// Delete the hook file of a shell, if present
+fn remove_hook_file(shell: ShellType) {
+    if let Ok(hook_path) = hook_file_path(shell) {
+        let _ = fs::remove_file(hook_path);
+    }
+}
+
+/// Rewrite existing hook files with the hooks of this uvup version
+///
+/// Run by `uvup update` through the new binary, so sessions started after an
+/// update pick up the new hooks without re-running `uvup init`.
+fn refresh_hook_files() -> Result<()> {
+    for shell in ALL_SHELLS {
+        let hook_path = hook_file_path(shell)?;
+        if hook_path.exists() {
+            write_atomic(&hook_path, &hook_file_content(shell))?;
+            println!("✓ Refreshed {}", hook_path.display());
+        }
+    }
+    Ok(())
+}
+
+/// A shell whose config file needs nothing but the line sourcing its hook file
+struct HookFileShell {
+    shell: ShellType,
+    label: &'static str,
+    config_path: PathBuf,
+}
+
+fn nushell_hook_file_shell() -> Result<HookFileShell> {
+    Ok(HookFileShell {
+        shell: ShellType::Nushell,
+        label: "Nushell",
+        config_path: get_nushell_config()?,
+    })
+}
+
+fn tcsh_hook_file_shell() -> Result<HookFileShell> {
+    Ok(HookFileShell {
+        shell: ShellType::Tcsh,
+        label: "tcsh",
+        config_path: get_tcsh_config()?,
+    })
+}
+
+fn xonsh_hook_file_shell() -> Result<HookFileShell> {
+    Ok(HookFileShell {
+        shell: ShellType::Xonsh,
+        label: "Xonsh",
+        config_path: get_xonsh_config()?,
+    })
+}
+
+fn initialize_hook_file_shell(shell: &HookFileShell, dry_run: bool) -> Result<()> {
+    let label = shell.label;
+    let config_path = &shell.config_path;
+    let hook_path = hook_file_path(shell.shell)?;
+
+    if dry_run {
+        println!("Would write: {}", hook_path.display());
+        if !config_path.exists() {
+            println!("Would create: {}", config_path.display());
+        }
+        println!("Would modify: {}", config_path.display());
+        println!("  Add uvup initialization to {label} config");
+        return Ok(());
+    }
+
+    let source_line = install_hook_file(shell.shell)?;
+
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let content = if config_path.exists() {
+        fs::read_to_string(config_path)?
+    } else {
+        String::new()
+    };
+
+    let new_content = rc_block::upsert_block(&content, &init_block(&source_line, None));
+
+    if new_content == content {
+        println!("{label} already up to date (skipped)");
+        return Ok(());
+    }
+
+    write_config_file(config_path, &new_content)?;
+
+    println!("✓ {label} initialized");
+    println!("  Config: {}", config_path.display());
+    println!("  Hook: {}", hook_path.display());
+    println!("  Please restart your {label} session");
+
+    Ok(())
+}
+
+fn uninitialize_hook_file_shell(shell: &HookFileShell, dry_run: bool) -> Result<()> {
+    let label = shell.label;
+    let config_path = &shell.config_path;
+
+    if !config_path.exists() {
+        println!("{label} config not found (skipped)");
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("Would modify: {}", config_path.display());
+        println!("  Remove uvup initialization from {label} config");
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(config_path)?;
+    let new_content = rc_block::remove_blocks(&content);
+
+    if new_content == content {
+        println!("{label} not initialized (skipped)");
+        return Ok(());
+    }
+
+    write_config_file(config_path, &new_content)?;
+    remove_hook_file(shell.shell);
+    println!("✓ Removed uvup from {label}");
+
+    Ok(())
+}
+
+/// Get Nushell config file path (`$nu.config-path`)
+fn get_nushell_config() -> Result<PathBuf> {
+    let reported = std::process::Command::new("nu")
+        .args(["--commands", "$nu.config-path"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|path| !path.is_empty());
+
+    if let Some(path) = reported {
+        return Ok(PathBuf::from(path));
+    }
+
+    // Nushell's default when it cannot be asked directly
+    if env::var_os("XDG_CONFIG_HOME").is_some() {
+        return Ok(get_xdg_config_home()?.join("nushell").join("config.nu"));
+    }
+
+    dirs::config_dir()
+        .map(|dir| dir.join("nushell").join("config.nu"))
+        .ok_or_else(|| UvupError::PathError("Could not determine config directory".to_string()))
+}
+
+/// Get tcsh config file path (`~/.tcshrc`, falling back to an existing `~/.cshrc`)
+fn get_tcsh_config() -> Result<PathBuf> {
+    let home = env::var("HOME").map_err(|_| UvupError::PathError("HOME not set".to_string()))?;
+    let home_path = PathBuf::from(home);
+
+    let tcshrc = home_path.join(".tcshrc");
+    if tcshrc.exists() {
+        return Ok(tcshrc);
+    }
+
+    let cshrc = home_path.join(".cshrc");
+    if cshrc.exists() {
+        return Ok(cshrc);
+    }
+
+    Ok(tcshrc)
+}
+
+/// Get Xonsh config file path (`~/.xonshrc`, or an existing `~/.config/xonsh/rc.xsh`)
+fn get_xonsh_config() -> Result<PathBuf> {
     let home = env::var("HOME")
         .or_else(|_| env::var("USERPROFILE"))
         .map_err(|_| UvupError::PathError("HOME/USERPROFILE not set".to_string()))?;
+    let home_path = PathBuf::from(home);
+
+    let xonshrc = home_path.join(".xonshrc");
+    if xonshrc.exists() {
+        return Ok(xonshrc);
+    }
+
+    let rc_xsh = get_xdg_config_home()?.join("xonsh").join("rc.xsh");
+    if rc_xsh.exists() {
+        return Ok(rc_xsh);
+    }
+
+    Ok(xonshrc)
+}
+
+/// Write a shell config file atomically, keeping a timestamped backup of the old version
+fn write_config_file(path: &Path, content: &str) -> Result<()> {
+    // Follow symlinks so files managed by dotfile tools stay linked
+    let target = if path.exists() {
+        fs::canonicalize(path)?
+    } else {
+        path.to_path_buf()
+    };
 
-    Ok(PathBuf::from(home)
-        .join(".config")
-        .join("fish")
-        .join("config.fish"))
+    if target.exists() {
+        let backup = backup_path(&target);
+        fs::copy(&target, &backup)?;
+        println!("  Backup: {}", backup.display());
+    }
+
+    write_atomic(&target, content)
 }
 
-/// Remove uvup initialization block from shell config content
-fn remove_init_block(content: &str) -> String {
-    let lines: Vec<&str> = content.lines().collect();
-    let mut result = Vec::new();
-    let mut skip = false;
+/// Get a timestamped backup path next to a config file
+fn backup_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{file_name}.uvup-backup-{}", compact_timestamp()))
+}
 
-    for line in lines {
-        if line.contains(INIT_MARKER_START) {
-            skip = true;
-            continue;
+/// Write a file by renaming a fully written temporary file over it,
+/// so a failed write never leaves a truncated file behind
+fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+
+    let mut builder = tempfile::Builder::new();
+    if let Ok(metadata) = fs::metadata(path) {
+        builder.permissions(metadata.permissions());
+    } else {
+        // Same mode as a freshly created file, after umask
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            builder.permissions(fs::Permissions::from_mode(0o666));
         }
-        if skip && (line.is_empty() || line.trim().is_empty()) {
-            skip = false;
+    }
+
+    let mut temp = builder.tempfile_in(parent)?;
+    temp.write_all(content.as_bytes())?;
+    temp.as_file().sync_all()?;
+    temp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// Initialization state of a single shell config file
+struct ShellStatus {
+    config_path: PathBuf,
+    /// Each uvup block found in the config file
+    blocks: Vec<rc_block::Block>,
+}
+
+/// Report which shells have uvup initialized (all supported shells unless one is given)
+fn print_status(requested_shell: Option<ShellType>) -> Result<()> {
+    let shells = match requested_shell {
+        Some(shell) => vec![shell],
+        None => ALL_SHELLS.to_vec(),
+    };
+    let mut initialized_count = 0;
+
+    for shell in shells {
+        let name = shell.name();
+        let Ok(status) = read_shell_status(shell) else {
+            println!("{name:<11} unavailable (config location unknown on this system)");
             continue;
-        }
-        if skip && line.contains("uvup init") {
+        };
+
+        if status.blocks.is_empty() {
+            println!(
+                "{name:<11} not initialized  {}",
+                status.config_path.display()
+            );
             continue;
         }
-        if !skip {
-            result.push(line);
+
+        initialized_count += 1;
+        println!(
+            "{name:<11} initialized      {}",
+            status.config_path.display()
+        );
+
+        if status.blocks.len() > 1 {
+            println!(
+                "  ! Duplicate uvup blocks found ({}), run 'uvup init {name}' to merge them",
+                status.blocks.len()
+            );
+        }
+
+        let expected = hook_source_line(shell)?;
+        let completions_line = completions_init_line(shell);
+        let up_to_date = status.blocks.iter().all(|block| {
+            !block.legacy
+                && block.lines.first() == Some(&expected)
+                && block.lines[1..]
+                    .iter()
+                    .all(|line| completions_line == Some(line.as_str()))
+        });
+        if !up_to_date {
+            println!(
+                "  ! Block differs from what this version of uvup installs, run 'uvup init {name}' to refresh it"
+            );
         }
+
+        let hook_path = hook_file_path(shell)?;
+        match fs::read_to_string(&hook_path) {
+            Ok(hook) if hook == hook_file_content(shell) => {}
+            Ok(_) => println!(
+                "  ! Hook file was written by another uvup version, run 'uvup init {name}' to refresh it"
+            ),
+            Err(_) => println!(
+                "  ! Hook file {} is missing, run 'uvup init {name}' to restore it",
+                hook_path.display()
+            ),
+        }
+    }
+
+    if initialized_count == 0 {
+        return Err(UvupError::NotInitialized);
     }
 
-    result.join("\n")
+    Ok(())
+}
+
+/// Read the uvup blocks from a shell's config file
+fn read_shell_status(shell: ShellType) -> Result<ShellStatus> {
+    let config_path = get_config_path(shell)?;
+    let content = if config_path.exists() {
+        fs::read_to_string(&config_path)?
+    } else {
+        String::new()
+    };
+
+    Ok(ShellStatus {
+        config_path,
+        blocks: rc_block::find_blocks(&content),
+    })
+}
+
+/// Get the config file `init` modifies for a shell
+fn get_config_path(shell: ShellType) -> Result<PathBuf> {
+    match shell {
+        ShellType::Bash => {
+            let home = env::var("HOME")
+                .or_else(|_| env::var("USERPROFILE"))
+                .map_err(|_| UvupError::PathError("HOME/USERPROFILE not set".to_string()))?;
+            Ok(PathBuf::from(home).join(".bashrc"))
+        }
+        ShellType::Zsh => {
+            #[cfg(target_os = "windows")]
+            {
+                Ok(get_zdotdir()?.join(".zshrc"))
+            }
+
+            #[cfg(not(target_os = "windows"))]
+            {
+                get_zsh_profile()
+            }
+        }
+        ShellType::Fish => get_fish_config(),
+        ShellType::PowerShell => get_powershell_profile(),
+        ShellType::Nushell => get_nushell_config(),
+        ShellType::Tcsh => get_tcsh_config(),
+        ShellType::Xonsh => get_xonsh_config(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_file_content_is_versioned() {
+        let content = hook_file_content(ShellType::Fish);
+        assert!(content.starts_with(&format!(
+            "# Generated by uvup {}",
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(content.ends_with(fish::FISH_HOOK));
+    }
+
+    #[test]
+    fn test_hook_source_line_points_at_hook_file() {
+        for shell in ALL_SHELLS {
+            let hook_path = hook_file_path(shell).unwrap();
+            let file_name = hook_path.file_name().unwrap().to_string_lossy();
+            assert!(
+                hook_source_line(shell)
+                    .unwrap()
+                    .contains(file_name.as_ref())
+            );
+        }
+        assert!(
+            hook_source_line(ShellType::PowerShell)
+                .unwrap()
+                .starts_with(". '")
+        );
+    }
+
+    #[test]
+    fn test_write_config_file_keeps_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let rc_path = dir.path().join(".bashrc");
+        fs::write(&rc_path, "# user content\n").unwrap();
+
+        write_config_file(&rc_path, "# user content\n# uvup\n").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&rc_path).unwrap(),
+            "# user content\n# uvup\n"
+        );
+        let backups: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(std::result::Result::ok)
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(".bashrc.uvup-backup-")
+            })
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(
+            fs::read_to_string(backups[0].path()).unwrap(),
+            "# user content\n"
+        );
+    }
+
+    #[test]
+    fn test_write_config_file_creates_new_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let rc_path = dir.path().join("config.fish");
+
+        write_config_file(&rc_path, "uvup init --raw | source\n").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&rc_path).unwrap(),
+            "uvup init --raw | source\n"
+        );
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
 }
@@ -0,0 +1,115 @@
+use crate::env::metadata::Licenses;
+use crate::env::paths::{get_venv_path, resolve_env};
+use crate::env::venv::{has_site_packages, installed_distributions};
+use crate::error::{Result, UvupError};
+use crate::utils::print_info;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// Shown for packages that declare no license
+const UNKNOWN_LICENSE: &str = "UNKNOWN";
+
+/// An installed package and its declared license
+struct Package {
+    name: String,
+    version: String,
+    license: String,
+    /// The deny rule the package's licenses match
+    denied_by: Option<String>,
+}
+
+/// Group the packages installed in an environment by license
+///
+/// Fails when a package matches a deny rule, or when the venv was never synced
+/// so that an empty report cannot pass for a clean one.
+pub(crate) fn run(name: Option<String>, deny: &[String], json: bool) -> Result<()> {
+    let name = resolve_env(name)?;
+
+    let venv_path = get_venv_path(&name)?;
+    if !has_site_packages(&venv_path) {
+        return Err(UvupError::SitePackagesMissing(name));
+    }
+
+    let packages: Vec<Package> = installed_distributions(&venv_path)
+        .into_iter()
+        .map(|dist| {
+            let licenses = Licenses::read(&dist.dist_info);
+            Package {
+                license: licenses
+                    .name()
+                    .unwrap_or_else(|| UNKNOWN_LICENSE.to_string()),
+                denied_by: licenses.denied_by(deny).map(str::to_string),
+                name: dist.name,
+                version: dist.version,
+            }
+        })
+        .collect();
+
+    let mut groups: BTreeMap<&str, Vec<&Package>> = BTreeMap::new();
+    for package in &packages {
+        groups.entry(&package.license).or_default().push(package);
+    }
+    let denied: Vec<&Package> = packages.iter().filter(|p| p.denied_by.is_some()).collect();
+
+    let mut stdout = io::stdout();
+
+    if json {
+        let licenses: serde_json::Map<String, Value> = groups
+            .iter()
+            .map(|(license, packages)| {
+                let packages: Vec<Value> = packages
+                    .iter()
+                    .map(|p| json!({ "name": p.name, "version": p.version }))
+                    .collect();
+                ((*license).to_string(), Value::from(packages))
+            })
+            .collect();
+        let denied_json: Vec<Value> = denied
+            .iter()
+            .map(|p| {
+                json!({
+                    "name": p.name,
+                    "version": p.version,
+                    "license": p.license,
+                    "rule": p.denied_by,
+                })
+            })
+            .collect();
+        let report = json!({ "env": name, "licenses": licenses, "denied": denied_json });
+        writeln!(stdout, "{report:#}")?;
+    } else if packages.is_empty() {
+        print_info(&format!("No packages installed in '{name}'"));
+    } else {
+        // Packages without license information are listed last
+        let unknown = groups.remove(UNKNOWN_LICENSE);
+        for (license, packages) in groups
+            .iter()
+            .chain(unknown.as_ref().map(|p| (&UNKNOWN_LICENSE, p)))
+        {
+            writeln!(stdout, "{license} ({})", packages.len())?;
+            for package in packages {
+                writeln!(stdout, "  {} {}", package.name, package.version)?;
+            }
+        }
+
+        if !denied.is_empty() {
+            writeln!(stdout, "\nDenied:")?;
+            for package in &denied {
+                writeln!(
+                    stdout,
+                    "  {} {} ({}) matches '{}'",
+                    package.name,
+                    package.version,
+                    package.license,
+                    package.denied_by.as_deref().unwrap_or_default()
+                )?;
+            }
+        }
+    }
+
+    if !denied.is_empty() {
+        return Err(UvupError::LicenseDenied(name, denied.len()));
+    }
+    Ok(())
+}
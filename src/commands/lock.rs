@@ -1,16 +1,11 @@
-use crate::env::paths::get_env_path;
+use crate::env::journal;
+use crate::env::paths::{get_env_path, resolve_env};
 use crate::error::{Result, UvupError};
-use std::env;
 use std::process::Command;
 
-pub(crate) fn run(upgrade: bool) -> Result<()> {
-    let active_env = env::var("UVUP_ACTIVE_ENV").map_err(|_| UvupError::NoActiveEnvironment)?;
-
-    let env_path = get_env_path(&active_env)?;
-
-    if !env_path.exists() {
-        return Err(UvupError::EnvNotFound(active_env));
-    }
+pub(crate) fn run(name: Option<String>, upgrade: bool) -> Result<()> {
+    let name = resolve_env(name)?;
+    let env_path = get_env_path(&name)?;
 
     let mut cmd = Command::new("uv");
     cmd.arg("--project").arg(&env_path).arg("lock");
@@ -19,15 +14,24 @@ pub(crate) fn run(upgrade: bool) -> Result<()> {
         cmd.arg("--upgrade");
     }
 
-    let status = cmd.status().map_err(|e| {
-        UvupError::CommandExecutionFailed(format!("Failed to execute uv lock: {e}"))
-    })?;
+    let mut run_lock = || {
+        let status = cmd.status().map_err(|e| {
+            UvupError::CommandExecutionFailed(format!("Failed to execute uv lock: {e}"))
+        })?;
 
-    if status.success() {
-        Ok(())
+        if status.success() {
+            Ok(())
+        } else {
+            Err(UvupError::CommandExecutionFailed(
+                "uv lock command failed".to_string(),
+            ))
+        }
+    };
+
+    // Only upgrades are journaled, plain locking does not change requirements
+    if upgrade {
+        journal::record(&name, run_lock)
     } else {
-        Err(UvupError::CommandExecutionFailed(
-            "uv lock command failed".to_string(),
-        ))
+        run_lock()
     }
 }
@@ -1,12 +1,31 @@
+pub(crate) mod activate_script;
 pub(crate) mod add;
+pub(crate) mod audit;
 pub(crate) mod clone;
+pub(crate) mod completions;
 pub(crate) mod create;
+pub(crate) mod deactivate_script;
 pub(crate) mod delete;
+pub(crate) mod diff;
+pub(crate) mod env_vars;
+pub(crate) mod history;
+pub(crate) mod hooks;
 pub(crate) mod init;
+pub(crate) mod licenses;
 pub(crate) mod list;
 pub(crate) mod lock;
 pub(crate) mod new;
+pub(crate) mod outdated;
+pub(crate) mod packages;
+pub(crate) mod prompt;
 pub(crate) mod remove;
+pub(crate) mod rollback;
+pub(crate) mod run;
+pub(crate) mod sbom;
+pub(crate) mod search_installed;
+pub(crate) mod snapshot;
 pub(crate) mod sync;
 pub(crate) mod tree;
+pub(crate) mod undo;
 pub(crate) mod update;
+pub(crate) mod why;
@@ -0,0 +1,306 @@
+use crate::env::index::{self, DEFAULT_INDEX_URL};
+use crate::env::lockfile::{Lockfile, Source};
+use crate::env::paths::{get_env_path, list_env_names, resolve_env};
+use crate::env::pep440::{Version, VersionSpecifiers};
+use crate::env::requirement::Requirement;
+use crate::error::{Result, UvupError};
+use crate::utils::{print_info, write_table};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{self, Write};
+use toml_edit::{DocumentMut, Item};
+
+/// Whether a newer version can be locked without editing `pyproject.toml`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Update {
+    /// The latest version satisfies the project's specifiers
+    InRange,
+    /// The specifiers must change to allow the latest version
+    OutOfRange,
+    /// Not a direct dependency, so the constraints come from other packages
+    Transitive,
+    /// The project's specifiers could not be parsed
+    Unknown,
+}
+
+impl Update {
+    fn label(self) -> &'static str {
+        match self {
+            Update::InRange => "in range",
+            Update::OutOfRange => "out of range",
+            Update::Transitive => "transitive",
+            Update::Unknown => "unknown",
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Update::InRange => "in-range",
+            Update::OutOfRange => "out-of-range",
+            Update::Transitive => "transitive",
+            Update::Unknown => "unknown",
+        }
+    }
+}
+
+/// A locked package with a newer version on its index
+struct Outdated {
+    env: String,
+    name: String,
+    current: String,
+    latest: String,
+    /// The project's specifiers, `None` for transitive packages
+    specifier: Option<String>,
+    update: Update,
+}
+
+/// A package whose index could not be queried
+struct Failure {
+    env: String,
+    name: String,
+    error: String,
+}
+
+/// Versions per index and package: `Ok(None)` when the index lacks it, `Err` when the request failed
+type IndexCache = HashMap<(String, String), std::result::Result<Option<Vec<Version>>, String>>;
+
+/// Report locked packages that have newer versions on the package index
+pub(crate) fn run(
+    name: Option<String>,
+    all: bool,
+    index_url: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let envs = if all {
+        list_env_names()?
+    } else {
+        vec![resolve_env(name)?]
+    };
+
+    let mut cache = IndexCache::new();
+    let mut outdated = Vec::new();
+    let mut failures = Vec::new();
+    for env in &envs {
+        let lock = match Lockfile::load(env) {
+            Ok(lock) => lock,
+            // With --all, environments that were never locked have nothing to report
+            Err(UvupError::LockfileMissing(_)) if all => continue,
+            Err(e) => return Err(e),
+        };
+        outdated.extend(check_env(env, &lock, index_url, &mut cache, &mut failures)?);
+    }
+
+    print_report(&outdated, &envs, all, json, failures.is_empty())?;
+
+    // Unreachable indexes only fail the run after everything else is reported
+    if failures.is_empty() {
+        return Ok(());
+    }
+    let mut stderr = io::stderr();
+    writeln!(stderr, "Could not check {} package(s):", failures.len())?;
+    for failure in &failures {
+        let package = if all {
+            format!("{} ({})", failure.name, failure.env)
+        } else {
+            failure.name.clone()
+        };
+        writeln!(stderr, "  {package}: {}", failure.error)?;
+    }
+    Err(UvupError::IndexRequestFailed(format!(
+        "{} package(s) could not be checked",
+        failures.len()
+    )))
+}
+
+/// Print the outdated packages; `complete` is false when some could not be checked
+fn print_report(
+    outdated: &[Outdated],
+    envs: &[String],
+    all: bool,
+    json: bool,
+    complete: bool,
+) -> Result<()> {
+    let mut stdout = io::stdout();
+
+    if json {
+        let rows: Vec<Value> = outdated
+            .iter()
+            .map(|row| {
+                json!({
+                    "env": row.env,
+                    "name": row.name,
+                    "current": row.current,
+                    "latest": row.latest,
+                    "specifier": row.specifier,
+                    "update": row.update.key(),
+                })
+            })
+            .collect();
+        writeln!(stdout, "{:#}", Value::from(rows))?;
+        return Ok(());
+    }
+
+    if outdated.is_empty() {
+        if !complete {
+            return Ok(());
+        }
+        if all {
+            print_info("All locked packages are up to date");
+        } else {
+            print_info(&format!(
+                "All packages locked in '{}' are up to date",
+                envs[0]
+            ));
+        }
+        return Ok(());
+    }
+
+    let mut header = vec!["NAME", "CURRENT", "LATEST", "SPECIFIER", "UPDATE"];
+    if all {
+        header.insert(0, "ENV");
+    }
+    let rows: Vec<Vec<&str>> = outdated
+        .iter()
+        .map(|row| {
+            let mut cells = vec![
+                row.name.as_str(),
+                row.current.as_str(),
+                row.latest.as_str(),
+                row.specifier.as_deref().unwrap_or("-"),
+                row.update.label(),
+            ];
+            if all {
+                cells.insert(0, row.env.as_str());
+            }
+            cells
+        })
+        .collect();
+    write_table(&mut stdout, &header, &rows)?;
+
+    if outdated
+        .iter()
+        .any(|row| matches!(row.update, Update::InRange | Update::Transitive))
+    {
+        print_info("\nRun 'uvup lock --upgrade' to lock the updates allowed by current specifiers");
+    }
+
+    Ok(())
+}
+
+/// Compare the registry packages of one lock file with their index
+fn check_env(
+    env: &str,
+    lock: &Lockfile,
+    index_url: Option<&str>,
+    cache: &mut IndexCache,
+    failures: &mut Vec<Failure>,
+) -> Result<Vec<Outdated>> {
+    let specifiers = direct_specifiers(env)?;
+
+    let mut outdated = Vec::new();
+    for package in lock.dependencies() {
+        // Git, URL and path sources have no index to compare with
+        let Source::Registry(registry) = &package.source else {
+            continue;
+        };
+        let Some(current) = package.version.as_deref().and_then(Version::parse) else {
+            continue;
+        };
+
+        let index = index_url.unwrap_or(if registry.is_empty() {
+            DEFAULT_INDEX_URL
+        } else {
+            registry
+        });
+        let key = (index.to_string(), package.name.clone());
+        let versions = cache.entry(key).or_insert_with(|| {
+            index::fetch_versions(index, &package.name).map_err(|e| match e {
+                UvupError::IndexRequestFailed(msg) => msg,
+                e => e.to_string(),
+            })
+        });
+        let versions = match versions {
+            Ok(Some(versions)) => versions,
+            Ok(None) => continue,
+            Err(error) => {
+                failures.push(Failure {
+                    env: env.to_string(),
+                    name: package.name.clone(),
+                    error: error.clone(),
+                });
+                continue;
+            }
+        };
+        let Some(latest) = index::latest(versions, current.is_prerelease()) else {
+            continue;
+        };
+        if *latest <= current {
+            continue;
+        }
+
+        let specifier = specifiers.get(&package.name).cloned();
+        let update = match &specifier {
+            None => Update::Transitive,
+            Some(spec) if spec.is_empty() => Update::InRange,
+            Some(spec) => match VersionSpecifiers::parse(spec) {
+                Ok(spec) if spec.contains(latest) => Update::InRange,
+                Ok(_) => Update::OutOfRange,
+                Err(_) => Update::Unknown,
+            },
+        };
+
+        outdated.push(Outdated {
+            env: env.to_string(),
+            name: package.name.clone(),
+            current: current.to_string(),
+            latest: latest.to_string(),
+            specifier,
+            update,
+        });
+    }
+
+    Ok(outdated)
+}
+
+/// Specifiers of each direct dependency, merged across groups and extras
+fn direct_specifiers(env: &str) -> Result<BTreeMap<String, String>> {
+    let path = get_env_path(env)?.join("pyproject.toml");
+    let content = fs::read_to_string(&path).map_err(|e| {
+        UvupError::PathError(format!("Failed to read pyproject.toml of '{env}': {e}"))
+    })?;
+    let doc: DocumentMut = content
+        .parse()
+        .map_err(|e| UvupError::PathError(format!("Failed to parse pyproject.toml: {e}")))?;
+
+    let project = doc.get("project");
+    let lists = project
+        .and_then(|p| p.get("dependencies"))
+        .into_iter()
+        .chain(groups(project.and_then(|p| p.get("optional-dependencies"))))
+        .chain(groups(doc.get("dependency-groups")));
+
+    let mut specifiers: BTreeMap<String, String> = BTreeMap::new();
+    for requirement in lists
+        .filter_map(Item::as_array)
+        .flatten()
+        .filter_map(|value| value.as_str())
+        .map(Requirement::parse)
+        .filter(|requirement| requirement.url.is_none())
+    {
+        let merged = specifiers.entry(requirement.name).or_default();
+        if !requirement.specifier.is_empty() {
+            if !merged.is_empty() {
+                merged.push(',');
+            }
+            merged.push_str(&requirement.specifier);
+        }
+    }
+    Ok(specifiers)
+}
+
+fn groups(item: Option<&Item>) -> impl Iterator<Item = &Item> {
+    item.and_then(Item::as_table_like)
+        .into_iter()
+        .flat_map(|table| table.iter().map(|(_, list)| list))
+}
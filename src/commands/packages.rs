@@ -0,0 +1,56 @@
+use crate::env::lockfile::Lockfile;
+use crate::env::paths::resolve_env;
+use crate::error::Result;
+use crate::utils::{print_info, write_table};
+use serde_json::{Value, json};
+use std::io::{self, Write};
+
+/// List the resolved packages of an environment, defaulting to the active one
+pub(crate) fn run(name: Option<String>, json: bool) -> Result<()> {
+    let name = resolve_env(name)?;
+
+    let lock = Lockfile::load(&name)?;
+    let direct = lock.direct_names();
+    let rows: Vec<(&str, &str, &str, &str)> = lock
+        .dependencies()
+        .map(|package| {
+            let kind = if direct.contains(&package.name) {
+                "direct"
+            } else {
+                "transitive"
+            };
+            (
+                package.name.as_str(),
+                package.version_or_dash(),
+                package.source.kind(),
+                kind,
+            )
+        })
+        .collect();
+
+    let mut stdout = io::stdout();
+
+    if json {
+        let packages: Vec<Value> = rows
+            .iter()
+            .map(|(name, version, source, kind)| {
+                json!({ "name": name, "version": version, "source": source, "kind": kind })
+            })
+            .collect();
+        writeln!(stdout, "{:#}", Value::from(packages))?;
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        print_info(&format!("No packages locked in '{name}'"));
+        return Ok(());
+    }
+
+    let table: Vec<Vec<&str>> = rows
+        .iter()
+        .map(|&(name, version, source, kind)| vec![name, version, source, kind])
+        .collect();
+    write_table(&mut stdout, &["NAME", "VERSION", "SOURCE", "KIND"], &table)?;
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::env::paths::{get_env_path, get_venv_path};
+use crate::env::venv::python_version;
+use crate::error::Result;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+const DEFAULT_FORMAT: &str = "{env} py{python}{stale}";
+const STALE_MARKER: &str = "*";
+
+/// Print prompt information about the active environment
+///
+/// Prints nothing when no environment is active, so it can be embedded in PS1
+/// or a starship `custom` module unconditionally.
+pub(crate) fn run(format: Option<&str>) -> Result<()> {
+    let Ok(active_env) = env::var("UVUP_ACTIVE_ENV") else {
+        return Ok(());
+    };
+
+    let Ok(env_path) = get_env_path(&active_env) else {
+        return Ok(());
+    };
+    if !env_path.exists() {
+        return Ok(());
+    }
+
+    let python = get_venv_path(&active_env)
+        .ok()
+        .and_then(|venv| python_version(&venv))
+        .unwrap_or_default();
+    let stale = if is_lock_stale(&env_path) {
+        STALE_MARKER
+    } else {
+        ""
+    };
+
+    let prompt = format
+        .unwrap_or(DEFAULT_FORMAT)
+        .replace("{env}", &active_env)
+        .replace("{python}", &python)
+        .replace("{stale}", stale);
+
+    writeln!(io::stdout(), "{prompt}")?;
+    Ok(())
+}
+
+/// Whether `uv.lock` is missing or older than `pyproject.toml`
+fn is_lock_stale(env_path: &Path) -> bool {
+    let modified = |name: &str| fs::metadata(env_path.join(name)).and_then(|m| m.modified());
+
+    match (modified("pyproject.toml"), modified("uv.lock")) {
+        (Ok(pyproject), Ok(lock)) => pyproject > lock,
+        (Ok(_), Err(_)) => true,
+        _ => false,
+    }
+}
@@ -1,35 +1,48 @@
-use crate::env::paths::get_env_path;
+use crate::env::journal;
+use crate::env::paths::{get_env_path, resolve_env};
+use crate::env::undo;
 use crate::error::{Result, UvupError};
-use std::env;
 use std::process::Command;
 
-pub(crate) fn run(packages: &[String], group: Option<String>) -> Result<()> {
-    let active_env = env::var("UVUP_ACTIVE_ENV").map_err(|_| UvupError::NoActiveEnvironment)?;
-
-    let env_path = get_env_path(&active_env)?;
-
-    if !env_path.exists() {
-        return Err(UvupError::EnvNotFound(active_env));
-    }
+pub(crate) fn run(
+    name: Option<String>,
+    packages: &[String],
+    optional: Option<String>,
+    group: Option<String>,
+    dev: bool,
+) -> Result<()> {
+    let name = resolve_env(name)?;
+    let env_path = get_env_path(&name)?;
 
     let mut cmd = Command::new("uv");
     cmd.arg("--project").arg(&env_path).arg("remove");
 
+    // --optional, --group and --dev select the table uv removes the package from
+    if let Some(extra) = optional {
+        cmd.arg("--optional").arg(extra);
+    }
     if let Some(g) = group {
         cmd.arg("--group").arg(g);
     }
+    if dev {
+        cmd.arg("--dev");
+    }
 
     cmd.args(packages);
 
-    let status = cmd.status().map_err(|e| {
-        UvupError::CommandExecutionFailed(format!("Failed to execute uv remove: {e}"))
-    })?;
-
-    if status.success() {
-        Ok(())
-    } else {
-        Err(UvupError::CommandExecutionFailed(
-            "uv remove command failed".to_string(),
-        ))
-    }
+    journal::record(&name, || {
+        undo::record(&name, || {
+            let status = cmd.status().map_err(|e| {
+                UvupError::CommandExecutionFailed(format!("Failed to execute uv remove: {e}"))
+            })?;
+
+            if status.success() {
+                Ok(())
+            } else {
+                Err(UvupError::CommandExecutionFailed(
+                    "uv remove command failed".to_string(),
+                ))
+            }
+        })
+    })
 }
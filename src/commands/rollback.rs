@@ -0,0 +1,78 @@
+// Allow println! in this module as it's used for user-facing output
+#![allow(clippy::print_stdout)]
+
+use crate::env::journal;
+use crate::env::paths::{ensure_env_exists, get_env_path};
+use crate::env::snapshots;
+use crate::error::{Result, UvupError};
+use crate::utils::print_success;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Restore an environment's project files from a snapshot and re-sync its venv
+pub(crate) fn run(name: &str, reference: &str) -> Result<()> {
+    ensure_env_exists(name)?;
+
+    let snapshot = snapshots::find(name, reference)?;
+    let env_path = get_env_path(name)?;
+
+    println!("Rolling back '{name}' to snapshot '{}'...", snapshot.id);
+    journal::record(name, || restore_and_sync(&env_path, &snapshot.path))?;
+
+    print_success(&format!(
+        "Rolled back '{name}' to snapshot '{}'",
+        snapshot.id
+    ));
+    Ok(())
+}
+
+/// Restore project files saved in a directory and re-sync the venv
+///
+/// The current files are put back if syncing fails.
+pub(crate) fn restore_and_sync(env_path: &Path, saved_dir: &Path) -> Result<()> {
+    let toml_path = env_path.join("pyproject.toml");
+    let lock_path = env_path.join("uv.lock");
+    let current_toml = fs::read(&toml_path)?;
+    let current_lock = fs::read(&lock_path).ok();
+
+    snapshots::restore_files(saved_dir, env_path)?;
+
+    println!("Installing packages...");
+    if let Err(e) = sync_venv(env_path, saved_dir.join("uv.lock").exists()) {
+        let _ = fs::write(&toml_path, current_toml);
+        match current_lock {
+            Some(lock) => {
+                let _ = fs::write(&lock_path, lock);
+            }
+            None => {
+                let _ = fs::remove_file(&lock_path);
+            }
+        }
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Sync the venv, installing exactly the restored lock file when there is one
+fn sync_venv(env_path: &Path, frozen: bool) -> Result<()> {
+    let mut cmd = Command::new("uv");
+    cmd.arg("--project").arg(env_path).arg("sync");
+
+    if frozen {
+        cmd.arg("--frozen");
+    }
+
+    let status = cmd.status().map_err(|e| {
+        UvupError::CommandExecutionFailed(format!("Failed to execute uv sync: {e}"))
+    })?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(UvupError::CommandExecutionFailed(
+            "uv sync command failed".to_string(),
+        ))
+    }
+}
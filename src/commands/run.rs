@@ -0,0 +1,72 @@
+use crate::commands::activate_script::activation_actions;
+use crate::env::paths::{get_venv_bin_dir, get_venv_path, validate_env_name};
+use crate::error::{Result, UvupError};
+use crate::shell::script::Action;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+/// Run a command inside an environment without activating it in the shell
+pub(crate) fn run(name: String, command: &[String]) -> Result<()> {
+    validate_env_name(&name)?;
+
+    let venv_path = get_venv_path(&name)?;
+    if !get_venv_bin_dir(&venv_path).exists() {
+        return Err(UvupError::EnvNotFound(name));
+    }
+
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| UvupError::CommandExecutionFailed("No command given".to_string()))?;
+
+    let mut child = Command::new(program);
+    child.args(args);
+    apply_actions(&mut child, &activation_actions(&name, venv_path, false)?);
+
+    let status = child.status().map_err(|e| {
+        UvupError::CommandExecutionFailed(format!("Failed to run '{program}': {e}"))
+    })?;
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+
+    Ok(())
+}
+
+/// Apply activation actions to the environment of a child process
+fn apply_actions(command: &mut Command, actions: &[Action]) {
+    let mut path: Vec<PathBuf> = env::var_os("PATH")
+        .map(|p| env::split_paths(&p).collect())
+        .unwrap_or_default();
+
+    for action in actions {
+        match action {
+            Action::DeactivateVenv => {
+                if let Some(active_venv) = env::var_os("VIRTUAL_ENV") {
+                    let active_bin = get_venv_bin_dir(Path::new(&active_venv));
+                    path.retain(|p| *p != active_bin);
+                    command.env_remove("VIRTUAL_ENV");
+                    command.env_remove("VIRTUAL_ENV_PROMPT");
+                }
+            }
+            Action::ActivateVenv(venv_path) => {
+                path.insert(0, get_venv_bin_dir(venv_path));
+                command.env("VIRTUAL_ENV", venv_path);
+                command.env_remove("PYTHONHOME");
+            }
+            Action::SetVar(key, value) => {
+                command.env(key, value);
+            }
+            Action::UnsetVar(key) => {
+                command.env_remove(key);
+            }
+            // Hook scripts only apply to interactive shells
+            Action::SourceScript(_) => {}
+        }
+    }
+
+    if let Ok(path) = env::join_paths(path) {
+        command.env("PATH", path);
+    }
+}
@@ -0,0 +1,595 @@
+use crate::env::lockfile::{Dependency, LockedPackage, Lockfile, Source, normalize_name};
+use crate::env::metadata::Licenses;
+use crate::env::paths::{get_venv_path, resolve_env};
+use crate::env::venv::installed_distributions;
+use crate::error::Result;
+use crate::utils::iso_timestamp;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A package in the bill of materials
+struct Component {
+    /// Unique reference, `<name>@<version>`
+    id: String,
+    name: String,
+    version: Option<String>,
+    purl: Option<String>,
+    /// `(algorithm, hex digest)` pairs from uv.lock
+    hashes: Vec<(String, String)>,
+    download: Option<String>,
+    licenses: Option<Licenses>,
+}
+
+/// Components of an environment and the dependencies between them
+struct Sbom {
+    env: String,
+    timestamp: String,
+    /// The environment's project
+    root: Component,
+    components: Vec<Component>,
+    /// Component ids each component depends on, keyed by id
+    dependencies: BTreeMap<String, Vec<String>>,
+}
+
+/// Output formats of `uvup sbom`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum SbomFormat {
+    #[value(name = "cyclonedx-json", help = "CycloneDX 1.5 JSON")]
+    CycloneDxJson,
+    #[value(name = "spdx-json", help = "SPDX 2.3 JSON")]
+    SpdxJson,
+}
+
+/// Print a software bill of materials for an environment
+pub(crate) fn run(name: Option<String>, format: SbomFormat) -> Result<()> {
+    let name = resolve_env(name)?;
+
+    let lock = Lockfile::load(&name)?;
+    let licenses: HashMap<String, Licenses> = installed_distributions(&get_venv_path(&name)?)
+        .into_iter()
+        .map(|dist| {
+            let licenses = Licenses::read(&dist.dist_info);
+            (dist.name, licenses)
+        })
+        .collect();
+
+    let sbom = Sbom::new(&name, &lock, licenses, iso_timestamp());
+    let document = match format {
+        SbomFormat::CycloneDxJson => sbom.to_cyclonedx(),
+        SbomFormat::SpdxJson => sbom.to_spdx(),
+    };
+    writeln!(io::stdout(), "{document:#}")?;
+    Ok(())
+}
+
+impl Sbom {
+    fn new(
+        env: &str,
+        lock: &Lockfile,
+        mut licenses: HashMap<String, Licenses>,
+        timestamp: String,
+    ) -> Self {
+        let root = lock.root().map_or_else(
+            || Component {
+                id: env.to_string(),
+                name: env.to_string(),
+                version: None,
+                purl: None,
+                hashes: Vec::new(),
+                download: None,
+                licenses: None,
+            },
+            |root| Component::new(root, None),
+        );
+        let components: Vec<Component> = lock
+            .dependencies()
+            .map(|package| Component::new(package, licenses.remove(&package.name)))
+            .collect();
+
+        // Dependencies name packages; the first one locked under a name stands for it
+        let mut ids: HashMap<&str, &str> = HashMap::new();
+        for (package, component) in lock.dependencies().zip(&components) {
+            ids.entry(package.name.as_str()).or_insert(&component.id);
+        }
+        let to_ids = |deps: Vec<&Dependency>| -> Vec<String> {
+            let mut depends_on: Vec<String> = deps
+                .into_iter()
+                .filter_map(|dep| ids.get(dep.name.as_str()).map(|id| (*id).to_string()))
+                .collect();
+            depends_on.sort();
+            depends_on.dedup();
+            depends_on
+        };
+
+        // The project depends on all its extras and groups; other packages only
+        // on the extras something requires them with
+        let extras = requested_extras(lock);
+        let mut dependencies = BTreeMap::new();
+        if let Some(package) = lock.root() {
+            dependencies.insert(
+                root.id.clone(),
+                to_ids(package.all_dependencies().collect()),
+            );
+        }
+        for (package, component) in lock.dependencies().zip(&components) {
+            let requested = extras
+                .get(package.name.as_str())
+                .map_or(&[][..], Vec::as_slice);
+            dependencies.insert(
+                component.id.clone(),
+                to_ids(package.edges(requested).collect()),
+            );
+        }
+
+        Self {
+            env: env.to_string(),
+            timestamp,
+            root,
+            components,
+            dependencies,
+        }
+    }
+
+    /// `CycloneDX` 1.5 JSON
+    fn to_cyclonedx(&self) -> Value {
+        let component = |component: &Component, kind: &str| {
+            let hashes: Vec<Value> = component
+                .hashes
+                .iter()
+                .filter_map(|(alg, content)| {
+                    let alg = match alg.as_str() {
+                        "md5" => "MD5",
+                        "sha1" => "SHA-1",
+                        "sha256" => "SHA-256",
+                        "sha384" => "SHA-384",
+                        "sha512" => "SHA-512",
+                        _ => return None,
+                    };
+                    Some(json!({ "alg": alg, "content": content }))
+                })
+                .collect();
+            let licenses = component.licenses.as_ref().and_then(|licenses| {
+                match (&licenses.expression, licenses.name()) {
+                    (Some(expression), _) => Some(json!([{ "expression": expression }])),
+                    (None, Some(name)) => Some(json!([{ "license": { "name": name } }])),
+                    (None, None) => None,
+                }
+            });
+
+            without_nulls(json!({
+                "type": kind,
+                "bom-ref": component.id,
+                "name": component.name,
+                "version": component.version,
+                "purl": component.purl,
+                "hashes": (!hashes.is_empty()).then_some(hashes),
+                "licenses": licenses,
+            }))
+        };
+
+        let dependencies: Vec<Value> = self
+            .dependencies
+            .iter()
+            .map(|(id, depends_on)| json!({ "ref": id, "dependsOn": depends_on }))
+            .collect();
+
+        json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "metadata": {
+                "timestamp": self.timestamp,
+                "tools": {
+                    "components": [
+                        { "type": "application", "name": "uvup", "version": TOOL_VERSION }
+                    ]
+                },
+                "component": component(&self.root, "application"),
+            },
+            "components": self
+                .components
+                .iter()
+                .map(|c| component(c, "library"))
+                .collect::<Vec<_>>(),
+            "dependencies": dependencies,
+        })
+    }
+
+    /// SPDX 2.3 JSON
+    fn to_spdx(&self) -> Value {
+        let package = |component: &Component| {
+            let checksums: Vec<Value> = component
+                .hashes
+                .iter()
+                .filter_map(|(alg, value)| {
+                    let algorithm = match alg.as_str() {
+                        "md5" => "MD5",
+                        "sha1" => "SHA1",
+                        "sha256" => "SHA256",
+                        "sha384" => "SHA384",
+                        "sha512" => "SHA512",
+                        _ => return None,
+                    };
+                    Some(json!({ "algorithm": algorithm, "checksumValue": value }))
+                })
+                .collect();
+            let external_refs = component.purl.as_ref().map(|purl| {
+                json!([{
+                    "referenceCategory": "PACKAGE-MANAGER",
+                    "referenceType": "purl",
+                    "referenceLocator": purl,
+                }])
+            });
+            let licenses = component.licenses.as_ref();
+            // Only SPDX expressions are valid here; other declarations become a comment
+            let declared = licenses
+                .and_then(|licenses| licenses.expression.clone())
+                .unwrap_or_else(|| "NOASSERTION".to_string());
+            let comment = licenses
+                .filter(|licenses| licenses.expression.is_none())
+                .and_then(Licenses::name)
+                .map(|name| format!("Declared in package metadata: {name}"));
+
+            without_nulls(json!({
+                "name": component.name,
+                "SPDXID": spdx_id(&component.id),
+                "versionInfo": component.version,
+                "downloadLocation": component.download.as_deref().unwrap_or("NOASSERTION"),
+                "filesAnalyzed": false,
+                "checksums": (!checksums.is_empty()).then_some(checksums),
+                "licenseConcluded": "NOASSERTION",
+                "licenseDeclared": declared,
+                "licenseComments": comment,
+                "externalRefs": external_refs,
+            }))
+        };
+
+        let mut relationships = vec![json!({
+            "spdxElementId": "SPDXRef-DOCUMENT",
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": spdx_id(&self.root.id),
+        })];
+        for (id, depends_on) in &self.dependencies {
+            for dependency in depends_on {
+                relationships.push(json!({
+                    "spdxElementId": spdx_id(id),
+                    "relationshipType": "DEPENDS_ON",
+                    "relatedSpdxElement": spdx_id(dependency),
+                }));
+            }
+        }
+
+        let packages: Vec<Value> = std::iter::once(&self.root)
+            .chain(&self.components)
+            .map(package)
+            .collect();
+
+        // The namespace must be unique per document
+        let mut digest = Sha256::new();
+        digest.update(self.env.as_bytes());
+        digest.update(self.timestamp.as_bytes());
+        for component in &self.components {
+            digest.update(component.id.as_bytes());
+        }
+        let unique = digest
+            .finalize()
+            .iter()
+            .take(8)
+            .fold(String::new(), |mut s, b| {
+                let _ = write!(s, "{b:02x}");
+                s
+            });
+
+        json!({
+            "spdxVersion": "SPDX-2.3",
+            "dataLicense": "CC0-1.0",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "name": self.env,
+            "documentNamespace": format!("https://spdx.org/spdxdocs/uvup-{}-{unique}", self.env),
+            "creationInfo": {
+                "created": self.timestamp,
+                "creators": [format!("Tool: uvup-{TOOL_VERSION}")],
+            },
+            "packages": packages,
+            "relationships": relationships,
+        })
+    }
+}
+
+/// Extras of each package that a dependency chain from the project requires
+fn requested_extras(lock: &Lockfile) -> HashMap<&str, Vec<String>> {
+    let mut packages: HashMap<&str, &LockedPackage> = HashMap::new();
+    for package in lock.dependencies() {
+        packages.entry(package.name.as_str()).or_insert(package);
+    }
+
+    let mut extras: HashMap<&str, Vec<String>> = HashMap::new();
+    let mut pending: Vec<&Dependency> = lock
+        .root()
+        .map(|root| root.all_dependencies().collect())
+        .unwrap_or_default();
+    while let Some(dep) = pending.pop() {
+        let Some(package) = packages.get(dep.name.as_str()) else {
+            continue;
+        };
+        let known = extras.get(package.name.as_str());
+        let first_visit = known.is_none();
+        let added: Vec<String> = dep
+            .extras
+            .iter()
+            .map(|extra| normalize_name(extra))
+            .filter(|extra| known.is_none_or(|known| !known.contains(extra)))
+            .collect();
+
+        // Follow the base dependencies once, and each extra when it is first required
+        if first_visit {
+            pending.extend(&package.dependencies);
+        }
+        for extra in &added {
+            pending.extend(
+                package
+                    .optional_dependencies
+                    .get(extra)
+                    .into_iter()
+                    .flatten(),
+            );
+        }
+        extras
+            .entry(package.name.as_str())
+            .or_default()
+            .extend(added);
+    }
+    extras
+}
+
+impl Component {
+    fn new(package: &LockedPackage, licenses: Option<Licenses>) -> Self {
+        let id = match &package.version {
+            Some(version) => format!("{}@{version}", package.name),
+            None => package.name.clone(),
+        };
+        let download = match &package.source {
+            Source::Git(url) => Some(format!("git+{url}")),
+            Source::Url(url) => Some(url.clone()),
+            Source::Registry(_) => package
+                .sdist
+                .iter()
+                .chain(&package.wheels)
+                .find_map(|artifact| artifact.url.clone()),
+            _ => None,
+        };
+
+        Self {
+            purl: purl(package),
+            hashes: package
+                .hashes()
+                .into_iter()
+                .filter_map(|hash| hash.split_once(':'))
+                .map(|(alg, digest)| (alg.to_string(), digest.to_string()))
+                .collect(),
+            name: package.name.clone(),
+            version: package.version.clone(),
+            id,
+            download,
+            licenses,
+        }
+    }
+}
+
+/// Package URL of a locked package; local and virtual projects have none
+fn purl(package: &LockedPackage) -> Option<String> {
+    let version = package.version.as_deref()?;
+    let qualifier = match &package.source {
+        Source::Registry(url) if url.trim_end_matches('/') == "https://pypi.org/simple" => None,
+        Source::Registry(url) => Some(("repository_url", url.clone())),
+        Source::Git(url) => Some(("vcs_url", format!("git+{url}"))),
+        Source::Url(url) => Some(("download_url", url.clone())),
+        Source::Path(_) | Source::Editable(_) | Source::Virtual(_) | Source::Unknown => {
+            return None;
+        }
+    };
+
+    let mut purl = format!(
+        "pkg:pypi/{}@{}",
+        percent_encode(&package.name),
+        percent_encode(version)
+    );
+    if let Some((key, value)) = qualifier {
+        let _ = write!(purl, "?{key}={}", percent_encode(&value));
+    }
+    Some(purl)
+}
+
+/// Percent-encode everything but the characters purl components keep as is
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~' | b':' | b'/') {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+/// SPDX element ids only allow letters, digits, `.` and `-`
+fn spdx_id(id: &str) -> String {
+    let id: String = id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("SPDXRef-{id}")
+}
+
+fn without_nulls(mut value: Value) -> Value {
+    if let Some(object) = value.as_object_mut() {
+        object.retain(|_, v| !v.is_null());
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCK: &str = r#"
+[[package]]
+name = "app"
+version = "0.1.0"
+source = { virtual = "." }
+dependencies = [{ name = "requests" }, { name = "tool" }]
+
+[[package]]
+name = "certifi"
+version = "2024.8.30"
+source = { registry = "https://pypi.org/simple" }
+sdist = { url = "https://files.example/certifi-2024.8.30.tar.gz", hash = "sha256:aaa" }
+wheels = [{ url = "https://files.example/certifi-2024.8.30-py3-none-any.whl", hash = "sha256:bbb" }]
+
+[[package]]
+name = "requests"
+version = "2.32.3"
+source = { registry = "https://pypi.org/simple" }
+dependencies = [{ name = "certifi" }]
+
+[[package]]
+name = "tool"
+version = "1.0+local"
+source = { git = "https://github.com/example/tool?rev=main#0123abc" }
+"#;
+
+    fn sbom() -> Sbom {
+        let lock = Lockfile::parse(LOCK).unwrap();
+        let licenses = HashMap::from([(
+            "certifi".to_string(),
+            Licenses {
+                expression: Some("MPL-2.0".to_string()),
+                ..Licenses::default()
+            },
+        )]);
+        Sbom::new("app", &lock, licenses, "2025-01-01T00:00:00Z".to_string())
+    }
+
+    #[test]
+    fn test_purl() {
+        let lock = Lockfile::parse(LOCK).unwrap();
+        let purls: Vec<Option<String>> = lock.packages.iter().map(purl).collect();
+        assert_eq!(purls[0], None);
+        assert_eq!(purls[1].as_deref(), Some("pkg:pypi/certifi@2024.8.30"));
+        assert_eq!(
+            purls[3].as_deref(),
+            Some(
+                "pkg:pypi/tool@1.0%2Blocal?vcs_url=git%2Bhttps://github.com/example/tool%3Frev%3Dmain%230123abc"
+            )
+        );
+    }
+
+    #[test]
+    fn test_cyclonedx() {
+        let bom = sbom().to_cyclonedx();
+
+        assert_eq!(bom["metadata"]["component"]["bom-ref"], "app@0.1.0");
+        let certifi = &bom["components"][0];
+        assert_eq!(certifi["purl"], "pkg:pypi/certifi@2024.8.30");
+        assert_eq!(
+            certifi["hashes"][1],
+            json!({ "alg": "SHA-256", "content": "bbb" })
+        );
+        assert_eq!(certifi["licenses"][0]["expression"], "MPL-2.0");
+
+        assert!(bom["dependencies"].as_array().unwrap().contains(
+            &json!({ "ref": "app@0.1.0", "dependsOn": ["requests@2.32.3", "tool@1.0+local"] })
+        ));
+    }
+
+    #[test]
+    fn test_dependencies_follow_requested_extras() {
+        let lock = r#"
+[[package]]
+name = "app"
+version = "0.1.0"
+source = { virtual = "." }
+dependencies = [{ name = "requests" }, { name = "tool" }]
+
+[package.optional-dependencies]
+cli = [{ name = "click" }]
+
+[[package]]
+name = "click"
+version = "8.1.7"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "pysocks"
+version = "1.7.1"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "requests"
+version = "2.32.3"
+source = { registry = "https://pypi.org/simple" }
+
+[package.optional-dependencies]
+socks = [{ name = "pysocks" }]
+
+[[package]]
+name = "tool"
+version = "1.0"
+source = { registry = "https://pypi.org/simple" }
+dependencies = [{ name = "pysocks" }]
+"#;
+        let depends_on = |lock: &str, id: &str| {
+            let lock = Lockfile::parse(lock).unwrap();
+            Sbom::new("app", &lock, HashMap::new(), String::new()).dependencies[id].clone()
+        };
+
+        assert_eq!(
+            depends_on(lock, "app@0.1.0"),
+            ["click@8.1.7", "requests@2.32.3", "tool@1.0"]
+        );
+        // pysocks is locked for tool, but nothing requires requests[socks]
+        assert!(depends_on(lock, "requests@2.32.3").is_empty());
+
+        let with_extra = lock.replace(
+            r#"dependencies = [{ name = "pysocks" }]"#,
+            r#"dependencies = [{ name = "requests", extra = ["socks"] }]"#,
+        );
+        assert_eq!(
+            depends_on(&with_extra, "requests@2.32.3"),
+            ["pysocks@1.7.1"]
+        );
+    }
+
+    #[test]
+    fn test_spdx() {
+        let doc = sbom().to_spdx();
+
+        let packages = doc["packages"].as_array().unwrap();
+        assert_eq!(packages.len(), 4);
+        assert_eq!(packages[1]["SPDXID"], "SPDXRef-certifi-2024.8.30");
+        assert_eq!(packages[1]["licenseDeclared"], "MPL-2.0");
+        assert_eq!(
+            packages[1]["downloadLocation"],
+            "https://files.example/certifi-2024.8.30.tar.gz"
+        );
+        assert_eq!(packages[2]["licenseDeclared"], "NOASSERTION");
+
+        assert!(doc["relationships"].as_array().unwrap().contains(&json!({
+            "spdxElementId": "SPDXRef-requests-2.32.3",
+            "relationshipType": "DEPENDS_ON",
+            "relatedSpdxElement": "SPDXRef-certifi-2024.8.30",
+        })));
+    }
+}
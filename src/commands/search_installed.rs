@@ -0,0 +1,129 @@
+use crate::env::lockfile::{Lockfile, normalize_name};
+use crate::env::paths::{get_venv_path, list_env_names};
+use crate::env::pep440::{Version, VersionSpecifiers};
+use crate::env::venv::installed_distributions;
+use crate::error::{Result, UvupError};
+use crate::utils::{print_info, write_table};
+use serde_json::{Value, json};
+use std::io::{self, Write};
+
+/// An environment containing the package
+struct Match {
+    env: String,
+    /// Version resolved in uv.lock
+    locked: Option<String>,
+    direct: bool,
+    /// Version installed in `.venv`, when requested
+    installed: Option<String>,
+}
+
+/// Find the environments whose lock file (or venv) contains a package
+pub(crate) fn run(package: &str, version: Option<&str>, installed: bool, json: bool) -> Result<()> {
+    let specifiers = version.map(VersionSpecifiers::parse).transpose()?;
+
+    let mut matches = Vec::new();
+    for env in list_env_names()? {
+        let Some(found) = find_in_env(&env, package, installed)? else {
+            continue;
+        };
+
+        // Without --version any locked entry matches, even one without a version
+        let Some(specifiers) = &specifiers else {
+            matches.push(found);
+            continue;
+        };
+        let satisfies = |version: &Option<String>| {
+            version
+                .as_deref()
+                .and_then(Version::parse)
+                .is_some_and(|v| specifiers.contains(&v))
+        };
+        if satisfies(&found.locked) || satisfies(&found.installed) {
+            matches.push(found);
+        }
+    }
+
+    let mut stdout = io::stdout();
+
+    if json {
+        let rows: Vec<Value> = matches
+            .iter()
+            .map(|m| {
+                json!({
+                    "env": m.env,
+                    "version": m.locked,
+                    "direct": m.direct,
+                    "installed": m.installed,
+                })
+            })
+            .collect();
+        writeln!(stdout, "{:#}", Value::from(rows))?;
+        return Ok(());
+    }
+
+    if matches.is_empty() {
+        let wanted = version.map_or_else(
+            || format!("'{package}'"),
+            |spec| format!("'{package}' matching '{spec}'"),
+        );
+        print_info(&format!("No environment contains {wanted}"));
+        return Ok(());
+    }
+
+    let mut header = vec!["ENV", "VERSION", "DIRECT"];
+    if installed {
+        header.push("INSTALLED");
+    }
+    let rows: Vec<Vec<&str>> = matches
+        .iter()
+        .map(|m| {
+            let mut row = vec![
+                m.env.as_str(),
+                m.locked.as_deref().unwrap_or("-"),
+                if m.direct { "yes" } else { "no" },
+            ];
+            if installed {
+                row.push(m.installed.as_deref().unwrap_or("-"));
+            }
+            row
+        })
+        .collect();
+    write_table(&mut stdout, &header, &rows)?;
+
+    Ok(())
+}
+
+/// Look a package up in an environment's lock file and optionally its venv
+fn find_in_env(env: &str, package: &str, installed: bool) -> Result<Option<Match>> {
+    let lock = match Lockfile::load(env) {
+        Ok(lock) => Some(lock),
+        // Environments that were never locked simply have no resolved packages
+        Err(UvupError::LockfileMissing(_)) => None,
+        Err(e) => return Err(e),
+    };
+    let locked = lock.as_ref().and_then(|lock| lock.package(package));
+
+    let installed_version = if installed {
+        let wanted = normalize_name(package);
+        installed_distributions(&get_venv_path(env)?)
+            .into_iter()
+            .find(|dist| dist.name == wanted)
+            .map(|dist| dist.version)
+    } else {
+        None
+    };
+
+    if locked.is_none() && installed_version.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(Match {
+        env: env.to_string(),
+        locked: locked.and_then(|p| p.version.clone()),
+        direct: locked.is_some_and(|p| {
+            lock.as_ref()
+                .is_some_and(|lock| lock.direct_names().contains(&p.name))
+        }),
+        installed: installed_version,
+    }))
+}
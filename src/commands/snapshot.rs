@@ -0,0 +1,43 @@
+use crate::env::paths::ensure_env_exists;
+use crate::env::snapshots;
+use crate::error::Result;
+use crate::utils::{print_info, print_success};
+use std::io::{self, Write};
+
+/// Save the pyproject.toml and uv.lock of an environment
+pub(crate) fn create(name: &str, label: Option<&str>) -> Result<()> {
+    ensure_env_exists(name)?;
+
+    let snapshot = snapshots::create(name, label)?;
+    print_success(&format!("Saved snapshot '{}' of '{name}'", snapshot.id));
+    print_info(&format!(
+        "Restore it with 'uvup rollback {name} {}'",
+        label.unwrap_or(&snapshot.id)
+    ));
+
+    Ok(())
+}
+
+/// List the snapshots of an environment, oldest first
+pub(crate) fn list(name: &str) -> Result<()> {
+    ensure_env_exists(name)?;
+
+    let snapshots = snapshots::list(name)?;
+    if snapshots.is_empty() {
+        print_info(&format!("No snapshots for '{name}'"));
+        return Ok(());
+    }
+
+    let mut stdout = io::stdout();
+    for snapshot in snapshots {
+        writeln!(
+            stdout,
+            "{:<20}  {}  {}",
+            snapshot.id,
+            snapshot.created,
+            snapshot.label.as_deref().unwrap_or("-")
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 // Allow println! in this module as it's used for user-facing output
 #![allow(clippy::print_stdout)]
 
-use crate::env::paths::get_env_path;
+use crate::env::journal;
+use crate::env::paths::{env_name_for_project, get_env_path};
+use crate::env::pyproject;
 use crate::error::{Result, UvupError};
 use crate::utils::print_success;
 use std::env;
@@ -37,8 +39,8 @@ pub(crate) fn run(
     }
 
     // Read current and template pyproject.toml
-    let current_doc = read_and_parse_toml(&current_toml_path)?;
-    let template_doc = read_and_parse_toml(&template_path.join("pyproject.toml"))?;
+    let current_doc = pyproject::read_and_parse_toml(&current_toml_path)?;
+    let template_doc = pyproject::read_and_parse_toml(&template_path.join("pyproject.toml"))?;
 
     // Process template
     let mut synced_doc = current_doc.clone();
@@ -47,10 +49,10 @@ pub(crate) fn run(
     sync_dependencies(&mut synced_doc, &template_doc, exclude, include);
 
     // Get Python versions
-    let current_python = get_python_version_from_toml(&current_doc)?;
-    let template_python = get_python_version_from_toml(&template_doc)?;
+    let current_python = pyproject::get_python_version_from_toml(&current_doc)?;
+    let template_python = pyproject::get_python_version_from_toml(&template_doc)?;
     let synced_python = if let Some(version) = python {
-        update_python_version(&mut synced_doc, version)?;
+        pyproject::update_python_version(&mut synced_doc, version)?;
         version.to_string()
     } else {
         current_python.clone()
@@ -75,20 +77,37 @@ pub(crate) fn run(
     // Sync project
     println!("Syncing project with template '{template}'...");
 
+    // Environments managed by uvup keep a journal of syncs
+    match env_name_for_project(&current_dir) {
+        Some(name) => journal::record(&name, || write_and_sync(&current_dir, &synced_doc))?,
+        None => write_and_sync(&current_dir, &synced_doc)?,
+    }
+
+    print_success(&format!(
+        "Successfully synced project with template '{template}'"
+    ));
+
+    Ok(())
+}
+
+/// Write the synced pyproject.toml and install it, restoring the original on failure
+fn write_and_sync(current_dir: &Path, synced_doc: &DocumentMut) -> Result<()> {
+    let toml_path = current_dir.join("pyproject.toml");
+
     // Backup current pyproject.toml
     let backup_path = current_dir.join("pyproject.toml.backup");
-    fs::copy(&current_toml_path, &backup_path)
+    fs::copy(&toml_path, &backup_path)
         .map_err(|e| UvupError::PathError(format!("Failed to backup pyproject.toml: {e}")))?;
 
     // Write updated pyproject.toml
-    fs::write(&current_toml_path, synced_doc.to_string())
+    fs::write(&toml_path, synced_doc.to_string())
         .map_err(|e| UvupError::PathError(format!("Failed to write pyproject.toml: {e}")))?;
 
     // Lock and sync
     println!("Installing packages...");
-    if let Err(e) = sync_environment(&current_dir) {
+    if let Err(e) = sync_environment(current_dir) {
         // Restore backup on error
-        let _ = fs::copy(&backup_path, &current_toml_path);
+        let _ = fs::copy(&backup_path, &toml_path);
         let _ = fs::remove_file(&backup_path);
         return Err(e);
     }
@@ -96,23 +115,9 @@ pub(crate) fn run(
     // Remove backup on success
     let _ = fs::remove_file(&backup_path);
 
-    print_success(&format!(
-        "Successfully synced project with template '{template}'"
-    ));
-
     Ok(())
 }
 
-/// Read and parse pyproject.toml
-fn read_and_parse_toml(path: &Path) -> Result<DocumentMut> {
-    let toml_content = fs::read_to_string(path)
-        .map_err(|e| UvupError::PathError(format!("Failed to read pyproject.toml: {e}")))?;
-
-    toml_content.parse::<DocumentMut>().map_err(|e| {
-        UvupError::CommandExecutionFailed(format!("Failed to parse pyproject.toml: {e}"))
-    })
-}
-
 /// Lock and sync packages
 fn sync_environment(project_path: &Path) -> Result<()> {
     println!("  Resolving and locking dependencies...");
@@ -161,7 +166,7 @@ fn sync_dependencies(
         .and_then(|p| p.get("dependencies"))
         .and_then(|d| d.as_array())
     {
-        let filtered = filter_dependency_array(template_deps, exclude, include);
+        let filtered = pyproject::filter_dependency_array(template_deps, exclude, include);
 
         // Update target dependencies
         if let Some(target_project) = target_doc.get_mut("project") {
@@ -176,24 +181,8 @@ fn sync_dependencies(
         .and_then(|p| p.get("optional-dependencies"))
         .and_then(|o| o.as_table())
     {
-        let mut synced_optional = toml_edit::Table::new();
-
-        for (group_name, group_deps) in template_optional {
-            if let Some(deps_array) = group_deps.as_array() {
-                let filtered = filter_dependency_array(deps_array, exclude, include);
-
-                if filtered.is_empty() {
-                    println!(
-                        "  Note: Optional group '{group_name}' is empty after filtering, skipping"
-                    );
-                } else {
-                    synced_optional.insert(
-                        group_name,
-                        Item::Value(Value::Array(toml_edit::Array::from_iter(filtered))),
-                    );
-                }
-            }
-        }
+        let mut synced_optional = template_optional.clone();
+        pyproject::filter_optional_dependencies(&mut synced_optional, exclude, include);
 
         // Update target optional-dependencies
         if let Some(target_project) = target_doc.get_mut("project") {
@@ -206,89 +195,16 @@ fn sync_dependencies(
             }
         }
     }
-}
 
-/// Filter a single dependency array
-fn filter_dependency_array(
-    deps_array: &toml_edit::Array,
-    exclude: Option<&[String]>,
-    include: Option<&[String]>,
-) -> Vec<toml_edit::Value> {
-    let mut filtered_deps = Vec::new();
-
-    for dep in deps_array {
-        if let Some(dep_str) = dep.as_str() {
-            let package_name = extract_package_name(dep_str);
-
-            if let Some(include_list) = include {
-                let included = include_list
-                    .iter()
-                    .any(|inc| package_name == inc.to_lowercase());
-                if !included {
-                    continue;
-                }
-            }
-
-            if let Some(exclude_list) = exclude {
-                let excluded = exclude_list
-                    .iter()
-                    .any(|exc| package_name == exc.to_lowercase());
-                if excluded {
-                    println!("  Excluding: {package_name}");
-                    continue;
-                }
-            }
-
-            filtered_deps.push(dep.clone());
-        }
+    // Sync dependency-groups
+    if let Some(template_groups) = template_doc
+        .get("dependency-groups")
+        .and_then(Item::as_table)
+    {
+        let mut synced_groups = template_groups.clone();
+        pyproject::filter_dependency_groups(&mut synced_groups, exclude, include);
+        target_doc["dependency-groups"] = Item::Table(synced_groups);
     }
-
-    filtered_deps
-}
-
-/// Extract package name from dependency string
-fn extract_package_name(dep_str: &str) -> String {
-    let end_pos = dep_str
-        .find(['=', '>', '<', '~', '!', '['])
-        .unwrap_or(dep_str.len());
-    dep_str[..end_pos].trim().to_lowercase()
-}
-
-/// Get Python version from pyproject.toml
-fn get_python_version_from_toml(doc: &DocumentMut) -> Result<String> {
-    let version_str = doc
-        .get("project")
-        .and_then(|p| p.get("requires-python"))
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| {
-            UvupError::CommandExecutionFailed(
-                "No requires-python found in pyproject.toml".to_string(),
-            )
-        })?;
-
-    let version = version_str
-        .trim_start_matches(|c: char| !c.is_ascii_digit())
-        .split('.')
-        .take(2)
-        .collect::<Vec<_>>()
-        .join(".");
-
-    Ok(version)
-}
-
-/// Update Python version in pyproject.toml
-fn update_python_version(doc: &mut DocumentMut, version: &str) -> Result<()> {
-    let requires_python = doc
-        .get_mut("project")
-        .and_then(|p| p.get_mut("requires-python"))
-        .ok_or_else(|| {
-            UvupError::CommandExecutionFailed(
-                "No requires-python found in pyproject.toml".to_string(),
-            )
-        })?;
-
-    *requires_python = Item::Value(Value::from(format!(">={version}")));
-    Ok(())
 }
 
 /// Print dry-run preview
@@ -337,15 +253,26 @@ fn print_dry_run_preview(
     compare_dependencies(current_doc, synced_doc);
     println!();
 
-    compare_optional_dependencies(current_doc, synced_doc);
+    pyproject::compare_groups(
+        "Optional dependencies",
+        &pyproject::extract_optional_dependencies(current_doc),
+        &pyproject::extract_optional_dependencies(synced_doc),
+        "Removed",
+    );
+    pyproject::compare_groups(
+        "Dependency groups",
+        &pyproject::extract_dependency_groups(current_doc),
+        &pyproject::extract_dependency_groups(synced_doc),
+        "Removed",
+    );
 
     println!("To sync this project, run the same command without --dry-run");
 }
 
 /// Compare dependencies
 fn compare_dependencies(current_doc: &DocumentMut, synced_doc: &DocumentMut) {
-    let current_deps = extract_dependencies(current_doc);
-    let synced_deps = extract_dependencies(synced_doc);
+    let current_deps = pyproject::extract_dependencies(current_doc);
+    let synced_deps = pyproject::extract_dependencies(synced_doc);
 
     let mut added = Vec::new();
     let mut removed = Vec::new();
@@ -385,84 +312,3 @@ fn compare_dependencies(current_doc: &DocumentMut, synced_doc: &DocumentMut) {
         }
     }
 }
-
-/// Compare optional-dependencies
-fn compare_optional_dependencies(current_doc: &DocumentMut, synced_doc: &DocumentMut) {
-    let current_optional = extract_optional_dependencies(current_doc);
-    let synced_optional = extract_optional_dependencies(synced_doc);
-
-    if current_optional.is_empty() && synced_optional.is_empty() {
-        return;
-    }
-
-    println!("Optional dependencies:");
-
-    let mut all_groups: std::collections::HashSet<String> =
-        current_optional.keys().cloned().collect();
-    all_groups.extend(synced_optional.keys().cloned());
-
-    let mut groups: Vec<_> = all_groups.into_iter().collect();
-    groups.sort();
-
-    for group in groups {
-        let current_deps = current_optional.get(&group);
-        let synced_deps = synced_optional.get(&group);
-
-        match (current_deps, synced_deps) {
-            (Some(cur), Some(syn)) if cur == syn => {
-                println!("  [{group}]: No changes");
-            }
-            (Some(_), Some(syn)) => {
-                println!("  [{group}]: Modified ({} packages)", syn.len());
-            }
-            (Some(_), None) => {
-                println!("  [{group}]: Removed");
-            }
-            (None, Some(syn)) => {
-                println!("  [{group}]: Added ({} packages)", syn.len());
-            }
-            (None, None) => unreachable!(),
-        }
-    }
-    println!();
-}
-
-/// Extract dependencies
-fn extract_dependencies(doc: &DocumentMut) -> Vec<String> {
-    doc.get("project")
-        .and_then(|p| p.get("dependencies"))
-        .and_then(|d| d.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str())
-                .map(String::from)
-                .collect()
-        })
-        .unwrap_or_default()
-}
-
-/// Extract optional-dependencies
-fn extract_optional_dependencies(
-    doc: &DocumentMut,
-) -> std::collections::HashMap<String, Vec<String>> {
-    let mut result = std::collections::HashMap::new();
-
-    if let Some(project) = doc.get("project") {
-        if let Some(optional) = project.get("optional-dependencies") {
-            if let Some(table) = optional.as_table() {
-                for (key, value) in table {
-                    if let Some(arr) = value.as_array() {
-                        let deps: Vec<String> = arr
-                            .iter()
-                            .filter_map(|v| v.as_str())
-                            .map(String::from)
-                            .collect();
-                        result.insert(key.to_string(), deps);
-                    }
-                }
-            }
-        }
-    }
-
-    result
-}
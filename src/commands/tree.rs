@@ -1,16 +1,10 @@
-use crate::env::paths::get_env_path;
+use crate::env::paths::{get_env_path, resolve_env};
 use crate::error::{Result, UvupError};
-use std::env;
 use std::process::Command;
 
-pub(crate) fn run(depth: Option<usize>) -> Result<()> {
-    let active_env = env::var("UVUP_ACTIVE_ENV").map_err(|_| UvupError::NoActiveEnvironment)?;
-
-    let env_path = get_env_path(&active_env)?;
-
-    if !env_path.exists() {
-        return Err(UvupError::EnvNotFound(active_env));
-    }
+pub(crate) fn run(name: Option<String>, depth: Option<usize>) -> Result<()> {
+    let name = resolve_env(name)?;
+    let env_path = get_env_path(&name)?;
 
     let mut cmd = Command::new("uv");
     cmd.arg("--project").arg(&env_path).arg("tree");
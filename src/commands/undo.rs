@@ -0,0 +1,29 @@
+// Allow println! in this module as it's used for user-facing output
+#![allow(clippy::print_stdout)]
+
+use crate::commands::rollback::restore_and_sync;
+use crate::env::journal;
+use crate::env::paths::{get_env_path, resolve_env};
+use crate::env::undo;
+use crate::error::{Result, UvupError};
+use crate::utils::print_success;
+
+/// Revert the last `add` or `remove` in an environment and re-sync it
+pub(crate) fn run(name: Option<String>) -> Result<()> {
+    let name = resolve_env(name)?;
+
+    let state = undo::load(&name)?.ok_or_else(|| UvupError::NothingToUndo(name.clone()))?;
+    let env_path = get_env_path(&name)?;
+
+    // Reverting over changes made since would silently discard them
+    if !state.is_current(&env_path) {
+        return Err(UvupError::UndoConflict(name, state.command));
+    }
+
+    println!("Undoing '{}'...", state.command);
+    journal::record(&name, || restore_and_sync(&env_path, &state.dir))?;
+    undo::clear(&name)?;
+
+    print_success(&format!("Undid '{}' in '{name}'", state.command));
+    Ok(())
+}
@@ -5,6 +5,7 @@ use crate::error::{Result, UvupError};
 use std::env;
 use std::fs;
 use std::io::Read;
+use std::process::Command;
 
 const GITHUB_API_RELEASES: &str = "https://api.github.com/repos/KercyDing/uvup/releases/latest";
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -53,6 +54,7 @@ pub(crate) fn run(check_only: bool) -> Result<()> {
     replace_binary(&binary_data)?;
 
     println!("Successfully updated to version {latest_version}!");
+    refresh_shell_hooks();
     println!("Please restart your shell to use the new version.");
 
     Ok(())
@@ -121,6 +123,22 @@ fn download_binary(url: &str) -> Result<Vec<u8>> {
     Ok(data)
 }
 
+/// Regenerate shell hook files by running the new binary
+///
+/// The running process still holds the old hooks, so the new binary has to write them.
+fn refresh_shell_hooks() {
+    let refreshed = env::current_exe().is_ok_and(|exe| {
+        Command::new(exe)
+            .args(["init", "--refresh-hooks"])
+            .status()
+            .is_ok_and(|status| status.success())
+    });
+
+    if !refreshed {
+        println!("Could not refresh shell hooks, run 'uvup init' to update them");
+    }
+}
+
 /// Replace the current binary with the new one
 fn replace_binary(data: &[u8]) -> Result<()> {
     let current_exe = env::current_exe()
@@ -0,0 +1,309 @@
+use crate::env::lockfile::{Dependency, LockedPackage, Lockfile};
+use crate::env::paths::resolve_env;
+use crate::error::{Result, UvupError};
+use crate::utils::print_info;
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Where a chain of dependencies starts in the project
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Origin {
+    Dependencies,
+    Optional(String),
+    Group(String),
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Origin::Dependencies => write!(f, "dependencies"),
+            Origin::Optional(extra) => write!(f, "optional '{extra}'"),
+            Origin::Group(group) => write!(f, "group '{group}'"),
+        }
+    }
+}
+
+/// A chain of packages from a direct dependency to the package asked about
+#[derive(Debug, PartialEq, Eq)]
+struct DependencyPath {
+    origin: Origin,
+    packages: Vec<String>,
+}
+
+/// Explain which direct dependencies pull a package into an environment
+pub(crate) fn run(package: &str, name: Option<String>, json: bool) -> Result<()> {
+    let name = resolve_env(name)?;
+
+    let lock = Lockfile::load(&name)?;
+    let target = lock
+        .package(package)
+        .ok_or_else(|| UvupError::PackageNotLocked(package.to_string(), name.clone()))?;
+    let paths = find_paths(&lock, &target.name);
+
+    let versions: HashMap<&str, &str> = lock
+        .dependencies()
+        .map(|package| (package.name.as_str(), package.version_or_dash()))
+        .collect();
+    let version = |package: &str| versions.get(package).copied().unwrap_or("-");
+
+    let mut stdout = io::stdout();
+
+    if json {
+        let paths: Vec<Value> = paths
+            .iter()
+            .map(|path| {
+                let (kind, group) = match &path.origin {
+                    Origin::Dependencies => ("dependencies", None),
+                    Origin::Optional(extra) => ("optional", Some(extra)),
+                    Origin::Group(group) => ("group", Some(group)),
+                };
+                let packages: Vec<Value> = path
+                    .packages
+                    .iter()
+                    .map(|package| json!({ "name": package, "version": version(package) }))
+                    .collect();
+                json!({ "origin": kind, "group": group, "packages": packages })
+            })
+            .collect();
+        let report = json!({
+            "env": name,
+            "package": target.name,
+            "version": target.version,
+            "paths": paths,
+        });
+        writeln!(stdout, "{report:#}")?;
+        return Ok(());
+    }
+
+    let target_label = format!("{} {}", target.name, target.version_or_dash());
+    if paths.is_empty() {
+        print_info(&format!(
+            "{target_label} is locked in '{name}' but no dependency requires it"
+        ));
+        return Ok(());
+    }
+
+    writeln!(stdout, "{target_label} is required in '{name}' by:")?;
+    for path in &paths {
+        let chain: Vec<String> = path
+            .packages
+            .iter()
+            .map(|package| format!("{package} {}", version(package)))
+            .collect();
+        writeln!(stdout, "  [{}] {}", path.origin, chain.join(" -> "))?;
+    }
+
+    Ok(())
+}
+
+/// Every path from the project's dependencies, extras and groups to a package
+fn find_paths(lock: &Lockfile, target: &str) -> Vec<DependencyPath> {
+    let Some(root) = lock.root() else {
+        return Vec::new();
+    };
+
+    let mut packages: HashMap<&str, &LockedPackage> = HashMap::new();
+    for package in lock.dependencies() {
+        packages.entry(package.name.as_str()).or_insert(package);
+    }
+    let leads_to_target = reverse_reachable(&packages, target);
+
+    let origins = std::iter::once((Origin::Dependencies, &root.dependencies))
+        .chain(
+            root.optional_dependencies
+                .iter()
+                .map(|(extra, deps)| (Origin::Optional(extra.clone()), deps)),
+        )
+        .chain(
+            root.dev_dependencies
+                .iter()
+                .map(|(group, deps)| (Origin::Group(group.clone()), deps)),
+        );
+
+    let mut paths = Vec::new();
+    for (origin, deps) in origins {
+        let mut found = Vec::new();
+        for dep in deps {
+            walk(
+                dep,
+                target,
+                &packages,
+                &leads_to_target,
+                &mut Vec::new(),
+                &mut found,
+            );
+        }
+        // The same package may be listed once per marker
+        for packages in found {
+            let path = DependencyPath {
+                origin: origin.clone(),
+                packages,
+            };
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+/// Follow a dependency edge, recording each chain that ends at the target
+fn walk(
+    dep: &Dependency,
+    target: &str,
+    packages: &HashMap<&str, &LockedPackage>,
+    leads_to_target: &HashSet<String>,
+    chain: &mut Vec<String>,
+    found: &mut Vec<Vec<String>>,
+) {
+    if !leads_to_target.contains(&dep.name) || chain.contains(&dep.name) {
+        return;
+    }
+
+    chain.push(dep.name.clone());
+    if dep.name == target {
+        found.push(chain.clone());
+    } else if let Some(package) = packages.get(dep.name.as_str()) {
+        for next in package.edges(&dep.extras) {
+            walk(next, target, packages, leads_to_target, chain, found);
+        }
+    }
+    chain.pop();
+}
+
+/// Packages from which the target can be reached, including the target itself
+fn reverse_reachable(packages: &HashMap<&str, &LockedPackage>, target: &str) -> HashSet<String> {
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for package in packages.values() {
+        let optional = package.optional_dependencies.values().flatten();
+        for dep in package.dependencies.iter().chain(optional) {
+            dependents
+                .entry(dep.name.as_str())
+                .or_default()
+                .push(package.name.as_str());
+        }
+    }
+
+    let mut reachable = HashSet::from([target.to_string()]);
+    let mut queue = VecDeque::from([target]);
+    while let Some(name) = queue.pop_front() {
+        for dependent in dependents.get(name).into_iter().flatten() {
+            if reachable.insert((*dependent).to_string()) {
+                queue.push_back(dependent);
+            }
+        }
+    }
+    reachable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCK: &str = r#"
+[[package]]
+name = "app"
+version = "0.1.0"
+source = { virtual = "." }
+dependencies = [
+    { name = "httpx" },
+    { name = "requests", extra = ["socks"] },
+]
+
+[package.optional-dependencies]
+cli = [{ name = "rich" }]
+
+[package.dev-dependencies]
+dev = [{ name = "pytest" }]
+
+[[package]]
+name = "httpx"
+version = "0.27.0"
+dependencies = [{ name = "certifi" }, { name = "idna" }]
+
+[[package]]
+name = "requests"
+version = "2.32.3"
+dependencies = [{ name = "certifi" }, { name = "idna" }]
+
+[package.optional-dependencies]
+socks = [{ name = "pysocks" }]
+
+[[package]]
+name = "rich"
+version = "13.7.1"
+dependencies = [{ name = "pygments" }]
+
+[[package]]
+name = "pytest"
+version = "8.3.2"
+dependencies = [{ name = "pygments" }]
+
+[package.optional-dependencies]
+dev = [{ name = "hypothesis" }]
+
+[[package]]
+name = "certifi"
+version = "2024.8.30"
+
+[[package]]
+name = "idna"
+version = "3.8"
+dependencies = [{ name = "certifi" }]
+
+[[package]]
+name = "pygments"
+version = "2.18.0"
+
+[[package]]
+name = "pysocks"
+version = "1.7.1"
+
+[[package]]
+name = "hypothesis"
+version = "6.112.0"
+"#;
+
+    fn chains(lock: &Lockfile, target: &str) -> Vec<(String, String)> {
+        find_paths(lock, target)
+            .into_iter()
+            .map(|path| (path.origin.to_string(), path.packages.join(" -> ")))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_paths() {
+        let lock = Lockfile::parse(LOCK).unwrap();
+        let path = |origin: &str, chain: &str| (origin.to_string(), chain.to_string());
+
+        assert_eq!(
+            chains(&lock, "certifi"),
+            [
+                path("dependencies", "httpx -> certifi"),
+                path("dependencies", "httpx -> idna -> certifi"),
+                path("dependencies", "requests -> certifi"),
+                path("dependencies", "requests -> idna -> certifi"),
+            ]
+        );
+        assert_eq!(
+            chains(&lock, "pygments"),
+            [
+                path("optional 'cli'", "rich -> pygments"),
+                path("group 'dev'", "pytest -> pygments"),
+            ]
+        );
+        assert_eq!(chains(&lock, "httpx"), [path("dependencies", "httpx")]);
+    }
+
+    #[test]
+    fn test_find_paths_follows_requested_extras_only() {
+        let lock = Lockfile::parse(LOCK).unwrap();
+
+        // requests is required with its 'socks' extra
+        assert_eq!(chains(&lock, "pysocks").len(), 1);
+        // pytest is required without its 'dev' extra
+        assert!(chains(&lock, "hypothesis").is_empty());
+    }
+}
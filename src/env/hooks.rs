@@ -0,0 +1,129 @@
+use crate::env::paths::get_env_meta_dir;
+use crate::error::{Result, UvupError};
+use crate::shell::detect::ShellType;
+use std::fs;
+use std::path::PathBuf;
+
+/// When an environment's hook scripts run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum HookKind {
+    /// Sourced after the environment is activated
+    Activate,
+    /// Sourced before the environment is deactivated
+    Deactivate,
+}
+
+impl HookKind {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            HookKind::Activate => "activate",
+            HookKind::Deactivate => "deactivate",
+        }
+    }
+}
+
+/// Get the directory holding an environment's hook scripts of one kind
+pub(crate) fn hook_dir(name: &str, kind: HookKind) -> Result<PathBuf> {
+    Ok(get_env_meta_dir(name)?.join(format!("{}.d", kind.name())))
+}
+
+/// Fail unless a script name is a plain file name inside the hook directory
+pub(crate) fn validate_script_name(script_name: &str) -> Result<()> {
+    let valid = !script_name.is_empty()
+        && script_name != "."
+        && script_name != ".."
+        && !script_name.contains(['/', '\\', ':']);
+
+    if !valid {
+        return Err(UvupError::InvalidHookName(script_name.to_string()));
+    }
+    Ok(())
+}
+
+/// Get the extension of hook scripts a shell sources
+///
+/// Nushell can only source files known at parse time, so it has no hook scripts.
+pub(crate) fn script_extension(shell: ShellType) -> Option<&'static str> {
+    match shell {
+        ShellType::Bash | ShellType::Zsh => Some("sh"),
+        ShellType::Fish => Some("fish"),
+        ShellType::PowerShell => Some("ps1"),
+        ShellType::Tcsh => Some("csh"),
+        ShellType::Xonsh => Some("xsh"),
+        ShellType::Nushell => None,
+    }
+}
+
+/// List all hook scripts of one kind, sorted by file name
+pub(crate) fn list_scripts(name: &str, kind: HookKind) -> Result<Vec<PathBuf>> {
+    let dir = hook_dir(name, kind)?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut scripts = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            scripts.push(path);
+        }
+    }
+
+    scripts.sort();
+    Ok(scripts)
+}
+
+/// List the hook scripts of one kind that a shell sources, in order
+pub(crate) fn shell_scripts(name: &str, kind: HookKind, shell: ShellType) -> Result<Vec<PathBuf>> {
+    let Some(extension) = script_extension(shell) else {
+        return Ok(Vec::new());
+    };
+
+    Ok(list_scripts(name, kind)?
+        .into_iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+        .collect())
+}
+
+/// Copy all hook scripts from one environment to another
+pub(crate) fn copy_scripts(source: &str, target: &str) -> Result<()> {
+    for kind in [HookKind::Activate, HookKind::Deactivate] {
+        let scripts = list_scripts(source, kind)?;
+        if scripts.is_empty() {
+            continue;
+        }
+
+        let target_dir = hook_dir(target, kind)?;
+        fs::create_dir_all(&target_dir)?;
+        for script in scripts {
+            if let Some(file_name) = script.file_name() {
+                fs::copy(&script, target_dir.join(file_name))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_extension() {
+        assert_eq!(script_extension(ShellType::Zsh), Some("sh"));
+        assert_eq!(script_extension(ShellType::PowerShell), Some("ps1"));
+        assert_eq!(script_extension(ShellType::Nushell), None);
+    }
+
+    #[test]
+    fn test_validate_script_name() {
+        assert!(validate_script_name("00-env").is_ok());
+        assert!(validate_script_name("load.secrets").is_ok());
+
+        assert!(validate_script_name("").is_err());
+        assert!(validate_script_name("..").is_err());
+        assert!(validate_script_name("../../x").is_err());
+        assert!(validate_script_name("/tmp/x").is_err());
+        assert!(validate_script_name("dir\\x").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! Client for the JSON form of the simple repository API (PEP 691)
+
+use crate::env::lockfile::normalize_name;
+use crate::env::pep440::Version;
+use crate::error::{Result, UvupError};
+use serde_json::Value;
+
+/// The index used when a lock file does not record one
+pub(crate) const DEFAULT_INDEX_URL: &str = "https://pypi.org/simple";
+
+const SIMPLE_JSON: &str = "application/vnd.pypi.simple.v1+json";
+
+/// Fetch the versions of a package with at least one file that is not yanked
+///
+/// Returns `None` if the index does not have the package.
+pub(crate) fn fetch_versions(index_url: &str, package: &str) -> Result<Option<Vec<Version>>> {
+    let url = format!(
+        "{}/{}/",
+        index_url.trim_end_matches('/'),
+        normalize_name(package)
+    );
+    let mut response = match ureq::get(&url)
+        .header("Accept", SIMPLE_JSON)
+        .header("User-Agent", &format!("uvup/{}", env!("CARGO_PKG_VERSION")))
+        .call()
+    {
+        Ok(response) => response,
+        Err(ureq::Error::StatusCode(404)) => return Ok(None),
+        Err(e) => return Err(UvupError::IndexRequestFailed(format!("{url}: {e}"))),
+    };
+
+    let project: Value = response
+        .body_mut()
+        .read_json()
+        .map_err(|e| UvupError::IndexRequestFailed(format!("{url}: invalid response: {e}")))?;
+
+    Ok(Some(available_versions(&project)))
+}
+
+/// The newest version, skipping pre-releases unless `pre` is set
+pub(crate) fn latest(versions: &[Version], pre: bool) -> Option<&Version> {
+    versions
+        .iter()
+        .filter(|version| pre || !version.is_prerelease())
+        .max()
+}
+
+/// Versions from a project page, taken from its files so yanked ones are skipped
+fn available_versions(project: &Value) -> Vec<Version> {
+    let files = project["files"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let mut versions: Vec<Version> = files
+        .iter()
+        .filter(|file| matches!(file["yanked"], Value::Null | Value::Bool(false)))
+        .filter_map(|file| file["filename"].as_str())
+        .filter_map(filename_version)
+        .filter_map(Version::parse)
+        .collect();
+
+    // Pages without files can still list versions (API 1.1)
+    if files.is_empty() {
+        versions = project["versions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .filter_map(Version::parse)
+            .collect();
+    }
+
+    versions.sort();
+    versions.dedup();
+    versions
+}
+
+/// Extract the version from a wheel or source distribution filename
+fn filename_version(filename: &str) -> Option<&str> {
+    if let Some(stem) = filename.strip_suffix(".whl") {
+        return stem.split('-').nth(1);
+    }
+
+    let stem = [".tar.gz", ".zip", ".tar.bz2", ".tgz"]
+        .iter()
+        .find_map(|ext| filename.strip_suffix(ext))?;
+    // Old source distributions may keep `-` in the name, but never in the version
+    stem.rsplit_once('-').map(|(_, version)| version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_available_versions() {
+        let project = json!({
+            "name": "python-dateutil",
+            "files": [
+                { "filename": "python-dateutil-2.8.2.tar.gz", "yanked": false },
+                { "filename": "python_dateutil-2.8.2-py2.py3-none-any.whl" },
+                { "filename": "python_dateutil-2.9.0-py2.py3-none-any.whl", "yanked": "broken" },
+                { "filename": "python_dateutil-3.0.0b1-py3-none-any.whl" },
+            ],
+        });
+
+        let versions = available_versions(&project);
+        let text: Vec<String> = versions.iter().map(ToString::to_string).collect();
+        assert_eq!(text, ["2.8.2", "3.0.0b1"]);
+
+        assert_eq!(
+            latest(&versions, false).map(ToString::to_string).as_deref(),
+            Some("2.8.2")
+        );
+        assert_eq!(
+            latest(&versions, true).map(ToString::to_string).as_deref(),
+            Some("3.0.0b1")
+        );
+    }
+
+    #[test]
+    fn test_available_versions_without_files() {
+        let project = json!({ "name": "demo", "files": [], "versions": ["1.0", "1.2"] });
+        assert_eq!(available_versions(&project).len(), 2);
+    }
+}
@@ -0,0 +1,261 @@
+use crate::env::paths::{get_env_meta_dir, get_env_path};
+use crate::error::{Result, UvupError};
+use crate::utils::iso_timestamp;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+const JOURNAL_FILE: &str = "history.jsonl";
+
+/// SHA-256 hashes of an environment's project files, `None` when a file is missing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FileHashes {
+    pub(crate) pyproject: Option<String>,
+    pub(crate) lock: Option<String>,
+}
+
+impl FileHashes {
+    /// Hash `pyproject.toml` and `uv.lock` in a project directory
+    pub(crate) fn read(project_dir: &Path) -> Self {
+        Self {
+            pyproject: hash_file(&project_dir.join("pyproject.toml")),
+            lock: hash_file(&project_dir.join("uv.lock")),
+        }
+    }
+}
+
+/// A mutating operation recorded in an environment's journal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Entry {
+    pub(crate) timestamp: String,
+    pub(crate) command: String,
+    /// User that ran the command, `None` when it could not be determined
+    pub(crate) user: Option<String>,
+    /// Machine the command ran on, `None` when it could not be determined
+    pub(crate) host: Option<String>,
+    pub(crate) before: FileHashes,
+    pub(crate) after: FileHashes,
+    /// Error message of a failed operation, `None` on success
+    pub(crate) error: Option<String>,
+}
+
+impl Entry {
+    pub(crate) fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+
+    pub(crate) fn to_json(&self) -> Value {
+        let mut value = json!({
+            "timestamp": self.timestamp,
+            "command": self.command,
+            "user": self.user,
+            "host": self.host,
+            "pyproject_before": self.before.pyproject,
+            "lock_before": self.before.lock,
+            "pyproject_after": self.after.pyproject,
+            "lock_after": self.after.lock,
+            "outcome": if self.succeeded() { "success" } else { "failure" },
+        });
+        if let Some(error) = &self.error {
+            value["error"] = Value::from(error.as_str());
+        }
+        value
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let text = |key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+        let succeeded = value.get("outcome")?.as_str()? == "success";
+
+        Some(Self {
+            timestamp: text("timestamp")?,
+            command: text("command")?,
+            user: text("user"),
+            host: text("host"),
+            before: FileHashes {
+                pyproject: text("pyproject_before"),
+                lock: text("lock_before"),
+            },
+            after: FileHashes {
+                pyproject: text("pyproject_after"),
+                lock: text("lock_after"),
+            },
+            error: if succeeded {
+                None
+            } else {
+                Some(text("error").unwrap_or_default())
+            },
+        })
+    }
+}
+
+/// Run a mutating operation on an environment and record it in the journal
+///
+/// Nothing is recorded if the environment does not exist afterwards, e.g.
+/// when a failed `create` cleaned up after itself. Failing to write the
+/// journal only prints a warning.
+pub(crate) fn record<T>(name: &str, operation: impl FnOnce() -> Result<T>) -> Result<T> {
+    let env_path = get_env_path(name)?;
+    let before = FileHashes::read(&env_path);
+
+    let result = operation();
+
+    if env_path.exists() {
+        let entry = Entry {
+            timestamp: iso_timestamp(),
+            command: command_line(),
+            user: current_user(),
+            host: current_host(),
+            before,
+            after: FileHashes::read(&env_path),
+            error: result.as_ref().err().map(error_summary),
+        };
+        if let Err(e) = append(name, &entry) {
+            let _ = writeln!(io::stderr(), "Warning: Failed to record history: {e}");
+        }
+    }
+
+    result
+}
+
+/// Append an entry to an environment's journal
+pub(crate) fn append(name: &str, entry: &Entry) -> Result<()> {
+    let meta_dir = get_env_meta_dir(name)?;
+    fs::create_dir_all(&meta_dir)?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(meta_dir.join(JOURNAL_FILE))?;
+    writeln!(file, "{}", entry.to_json())?;
+    Ok(())
+}
+
+/// Read an environment's journal, oldest entry first
+///
+/// Lines that cannot be parsed (e.g. a write cut short) are skipped.
+pub(crate) fn read(name: &str) -> Result<Vec<Entry>> {
+    let path = get_env_meta_dir(name)?.join(JOURNAL_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| UvupError::PathError(format!("Failed to read {}: {e}", path.display())))?;
+
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|value| Entry::from_json(&value))
+        .collect())
+}
+
+fn hash_file(path: &Path) -> Option<String> {
+    let content = fs::read(path).ok()?;
+    let digest = Sha256::digest(&content);
+    Some(
+        digest
+            .iter()
+            .fold(String::with_capacity(64), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            }),
+    )
+}
+
+/// The uvup command line being run, quoted for display
+pub(crate) fn command_line() -> String {
+    let mut parts = vec!["uvup".to_string()];
+    parts.extend(env::args().skip(1).map(|arg| {
+        if arg.is_empty() || arg.contains(char::is_whitespace) {
+            format!("'{}'", arg.replace('\'', "'\\''"))
+        } else {
+            arg
+        }
+    }));
+    parts.join(" ")
+}
+
+/// Login name of the current user, from `USER` or `USERNAME` on Windows
+fn current_user() -> Option<String> {
+    ["USER", "USERNAME"]
+        .into_iter()
+        .find_map(|key| env::var(key).ok().filter(|value| !value.is_empty()))
+}
+
+/// Name of the current machine
+///
+/// `HOSTNAME` is usually a shell variable rather than exported, so on Unix
+/// this falls back to `/etc/hostname`.
+fn current_host() -> Option<String> {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .into_iter()
+        .find_map(|key| env::var(key).ok())
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+}
+
+/// First line of an error, without the "Error: " prefix
+fn error_summary(error: &UvupError) -> String {
+    let message = error.to_string();
+    let first_line = message.lines().next().unwrap_or_default();
+    first_line
+        .strip_prefix("Error: ")
+        .unwrap_or(first_line)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_json_round_trip() {
+        let entry = Entry {
+            timestamp: "2025-11-19T14:25:30Z".to_string(),
+            command: "uvup add requests".to_string(),
+            user: Some("alice".to_string()),
+            host: None,
+            before: FileHashes {
+                pyproject: Some("aa".to_string()),
+                lock: None,
+            },
+            after: FileHashes {
+                pyproject: Some("bb".to_string()),
+                lock: Some("cc".to_string()),
+            },
+            error: None,
+        };
+        let json = entry.to_json();
+        assert_eq!(json["outcome"], "success");
+        assert_eq!(json["lock_before"], Value::Null);
+        assert_eq!(json["user"], "alice");
+        assert_eq!(json["host"], Value::Null);
+        assert_eq!(Entry::from_json(&json), Some(entry.clone()));
+
+        let failed = Entry {
+            error: Some("uv add command failed".to_string()),
+            ..entry
+        };
+        let json = failed.to_json();
+        assert_eq!(json["outcome"], "failure");
+        assert_eq!(Entry::from_json(&json), Some(failed));
+    }
+
+    #[test]
+    fn test_hash_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pyproject.toml");
+        fs::write(&path, "abc").unwrap();
+
+        assert_eq!(
+            hash_file(&path).as_deref(),
+            Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(hash_file(&dir.path().join("uv.lock")), None);
+    }
+}
@@ -0,0 +1,412 @@
+//! Typed model of `uv.lock`
+//!
+//! Only the parts uvup reports on are modelled: packages, their versions and
+//! sources, and the dependency edges between them.
+
+use crate::env::paths::get_env_path;
+use crate::error::{Result, UvupError};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use toml_edit::{DocumentMut, InlineTable, Item, Table};
+
+/// A parsed `uv.lock`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Lockfile {
+    pub(crate) requires_python: Option<String>,
+    pub(crate) packages: Vec<LockedPackage>,
+}
+
+/// A resolved package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LockedPackage {
+    pub(crate) name: String,
+    /// Missing for packages whose version is computed at build time
+    pub(crate) version: Option<String>,
+    pub(crate) source: Source,
+    pub(crate) dependencies: Vec<Dependency>,
+    /// Dependencies of each extra
+    pub(crate) optional_dependencies: BTreeMap<String, Vec<Dependency>>,
+    /// Dependencies of each dependency group (only set on workspace members)
+    pub(crate) dev_dependencies: BTreeMap<String, Vec<Dependency>>,
+    pub(crate) sdist: Option<Artifact>,
+    pub(crate) wheels: Vec<Artifact>,
+}
+
+/// A distribution file of a package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Artifact {
+    pub(crate) url: Option<String>,
+    /// Hash in `<algorithm>:<hex>` form, e.g. `sha256:...`
+    pub(crate) hash: Option<String>,
+}
+
+/// Where a package was resolved from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Source {
+    /// A package index, with its URL
+    Registry(String),
+    /// A Git repository, with the URL including the resolved commit
+    Git(String),
+    /// A direct URL to a wheel or source distribution
+    Url(String),
+    /// A local file or directory
+    Path(String),
+    /// A local directory installed in editable mode
+    Editable(String),
+    /// A project without a build system, such as uvup's environments
+    Virtual(String),
+    Unknown,
+}
+
+/// An edge from a package to one of its dependencies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Dependency {
+    pub(crate) name: String,
+    pub(crate) extras: Vec<String>,
+    pub(crate) marker: Option<String>,
+}
+
+impl Source {
+    /// Short name of the kind of source
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Source::Registry(_) => "registry",
+            Source::Git(_) => "git",
+            Source::Url(_) => "url",
+            Source::Path(_) => "path",
+            Source::Editable(_) => "editable",
+            Source::Virtual(_) => "virtual",
+            Source::Unknown => "unknown",
+        }
+    }
+
+    /// Whether this is the source of a workspace member rather than a dependency
+    fn is_local_project(&self) -> bool {
+        matches!(self, Source::Editable(path) | Source::Virtual(path) if path == ".")
+    }
+
+    fn parse(table: &InlineTable) -> Self {
+        let value = |key: &str| table.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+        if let Some(url) = value("registry") {
+            Source::Registry(url)
+        } else if let Some(url) = value("git") {
+            Source::Git(url)
+        } else if let Some(url) = value("url") {
+            Source::Url(url)
+        } else if let Some(path) = value("path").or_else(|| value("directory")) {
+            Source::Path(path)
+        } else if let Some(path) = value("editable") {
+            Source::Editable(path)
+        } else if let Some(path) = value("virtual") {
+            Source::Virtual(path)
+        } else {
+            Source::Unknown
+        }
+    }
+}
+
+impl Lockfile {
+    /// Load the lock file of an environment
+    pub(crate) fn load(name: &str) -> Result<Self> {
+        let path = get_env_path(name)?.join("uv.lock");
+        if !path.exists() {
+            return Err(UvupError::LockfileMissing(name.to_string()));
+        }
+        Self::read(&path)
+    }
+
+    /// Read and parse a lock file
+    pub(crate) fn read(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| UvupError::PathError(format!("Failed to read {}: {e}", path.display())))?;
+        Self::parse(&content)
+    }
+
+    /// Parse the content of a lock file
+    pub(crate) fn parse(content: &str) -> Result<Self> {
+        let doc: DocumentMut = content
+            .parse()
+            .map_err(|e| UvupError::PathError(format!("Failed to parse uv.lock: {e}")))?;
+
+        let packages = doc
+            .get("package")
+            .and_then(Item::as_array_of_tables)
+            .into_iter()
+            .flat_map(|packages| packages.iter())
+            .filter_map(LockedPackage::parse)
+            .collect();
+
+        Ok(Self {
+            requires_python: doc
+                .get("requires-python")
+                .and_then(Item::as_str)
+                .map(str::to_string),
+            packages,
+        })
+    }
+
+    /// Find a package by name
+    pub(crate) fn package(&self, name: &str) -> Option<&LockedPackage> {
+        let name = normalize_name(name);
+        self.packages.iter().find(|package| package.name == name)
+    }
+
+    /// The project the lock file belongs to
+    ///
+    /// uvup environments are virtual projects at the lock file's directory.
+    pub(crate) fn root(&self) -> Option<&LockedPackage> {
+        self.packages
+            .iter()
+            .find(|package| package.source.is_local_project())
+    }
+
+    /// Names of the packages the project depends on directly, in any group or extra
+    pub(crate) fn direct_names(&self) -> BTreeSet<String> {
+        self.root()
+            .map(|root| {
+                root.all_dependencies()
+                    .map(|dep| dep.name.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Resolved packages other than the project itself
+    pub(crate) fn dependencies(&self) -> impl Iterator<Item = &LockedPackage> {
+        self.packages
+            .iter()
+            .filter(|package| !package.source.is_local_project())
+    }
+}
+
+impl LockedPackage {
+    fn parse(table: &Table) -> Option<Self> {
+        let groups = |key: &str| -> BTreeMap<String, Vec<Dependency>> {
+            table
+                .get(key)
+                .and_then(Item::as_table_like)
+                .into_iter()
+                .flat_map(|groups| groups.iter())
+                .map(|(group, deps)| (group.to_string(), parse_dependencies(deps)))
+                .collect()
+        };
+
+        Some(Self {
+            name: normalize_name(table.get("name")?.as_str()?),
+            version: table
+                .get("version")
+                .and_then(Item::as_str)
+                .map(str::to_string),
+            source: table
+                .get("source")
+                .and_then(Item::as_inline_table)
+                .map_or(Source::Unknown, Source::parse),
+            dependencies: table
+                .get("dependencies")
+                .map(parse_dependencies)
+                .unwrap_or_default(),
+            optional_dependencies: groups("optional-dependencies"),
+            dev_dependencies: groups("dev-dependencies"),
+            sdist: table
+                .get("sdist")
+                .and_then(Item::as_inline_table)
+                .map(Artifact::parse),
+            wheels: table
+                .get("wheels")
+                .and_then(Item::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|wheel| wheel.as_inline_table().map(Artifact::parse))
+                .collect(),
+        })
+    }
+
+    /// Hashes of the source distribution and wheels, without duplicates
+    pub(crate) fn hashes(&self) -> Vec<&str> {
+        let mut hashes = Vec::new();
+        for hash in self
+            .sdist
+            .iter()
+            .chain(&self.wheels)
+            .filter_map(|artifact| artifact.hash.as_deref())
+        {
+            if !hashes.contains(&hash) {
+                hashes.push(hash);
+            }
+        }
+        hashes
+    }
+
+    /// Dependencies of the package when it is required with the given extras
+    pub(crate) fn edges<'a>(
+        &'a self,
+        extras: &'a [String],
+    ) -> impl Iterator<Item = &'a Dependency> {
+        self.dependencies.iter().chain(
+            extras
+                .iter()
+                .filter_map(|extra| self.optional_dependencies.get(&normalize_name(extra)))
+                .flatten(),
+        )
+    }
+
+    /// Dependencies of the package, its extras and its dependency groups
+    pub(crate) fn all_dependencies(&self) -> impl Iterator<Item = &Dependency> {
+        self.dependencies.iter().chain(
+            self.optional_dependencies
+                .values()
+                .chain(self.dev_dependencies.values())
+                .flatten(),
+        )
+    }
+
+    /// The version, or `-` when the lock file has none
+    pub(crate) fn version_or_dash(&self) -> &str {
+        self.version.as_deref().unwrap_or("-")
+    }
+}
+
+impl Artifact {
+    fn parse(table: &InlineTable) -> Self {
+        let value = |key: &str| table.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        Self {
+            url: value("url"),
+            hash: value("hash"),
+        }
+    }
+}
+
+fn parse_dependencies(item: &Item) -> Vec<Dependency> {
+    item.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|value| {
+            let table = value.as_inline_table()?;
+            Some(Dependency {
+                name: normalize_name(table.get("name")?.as_str()?),
+                extras: table
+                    .get("extra")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|extra| extra.as_str().map(str::to_string))
+                    .collect(),
+                marker: table
+                    .get("marker")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Normalize a package name as in PEP 503
+pub(crate) fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase().replace(['_', '.'], "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCK: &str = r#"
+version = 1
+requires-python = ">=3.12"
+
+[[package]]
+name = "certifi"
+version = "2024.8.30"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "myproject"
+version = "0.1.0"
+source = { virtual = "." }
+dependencies = [
+    { name = "requests", extra = ["socks"] },
+]
+
+[package.dev-dependencies]
+dev = [
+    { name = "tool", marker = "sys_platform == 'linux'" },
+]
+
+[[package]]
+name = "requests"
+version = "2.32.3"
+source = { registry = "https://pypi.org/simple" }
+dependencies = [
+    { name = "certifi" },
+]
+sdist = { url = "https://files.example/requests-2.32.3.tar.gz", hash = "sha256:aaa", size = 1 }
+wheels = [
+    { url = "https://files.example/requests-2.32.3-py3-none-any.whl", hash = "sha256:bbb", size = 1 },
+]
+
+[package.optional-dependencies]
+socks = [
+    { name = "PySocks" },
+]
+
+[[package]]
+name = "tool"
+source = { git = "https://github.com/example/tool?rev=main#0123abc" }
+"#;
+
+    #[test]
+    fn test_parse_lockfile() {
+        let lock = Lockfile::parse(LOCK).unwrap();
+        assert_eq!(lock.requires_python.as_deref(), Some(">=3.12"));
+        assert_eq!(lock.packages.len(), 4);
+
+        let requests = lock.package("Requests").unwrap();
+        assert_eq!(requests.version.as_deref(), Some("2.32.3"));
+        assert_eq!(requests.source.kind(), "registry");
+        assert_eq!(requests.dependencies[0].name, "certifi");
+        assert_eq!(requests.optional_dependencies["socks"][0].name, "pysocks");
+        assert_eq!(requests.hashes(), ["sha256:aaa", "sha256:bbb"]);
+        assert_eq!(
+            requests
+                .sdist
+                .as_ref()
+                .and_then(|sdist| sdist.url.as_deref()),
+            Some("https://files.example/requests-2.32.3.tar.gz")
+        );
+
+        let tool = lock.package("tool").unwrap();
+        assert_eq!(tool.version, None);
+        assert_eq!(tool.source.kind(), "git");
+
+        let root = lock.root().unwrap();
+        assert_eq!(root.name, "myproject");
+        assert_eq!(root.dependencies[0].extras, vec!["socks".to_string()]);
+        assert_eq!(
+            root.dev_dependencies["dev"][0].marker.as_deref(),
+            Some("sys_platform == 'linux'")
+        );
+    }
+
+    #[test]
+    fn test_direct_names_and_dependencies() {
+        let lock = Lockfile::parse(LOCK).unwrap();
+
+        assert_eq!(
+            lock.direct_names().into_iter().collect::<Vec<_>>(),
+            ["requests", "tool"]
+        );
+        assert_eq!(
+            lock.dependencies()
+                .map(|package| package.name.as_str())
+                .collect::<Vec<_>>(),
+            ["certifi", "requests", "tool"]
+        );
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("Typing_Extensions"), "typing-extensions");
+        assert_eq!(normalize_name("zope.interface"), "zope-interface");
+    }
+}
@@ -0,0 +1,161 @@
+//! License fields of installed distributions' core metadata
+
+use std::fs;
+use std::path::Path;
+
+/// Longest `License` field treated as a name rather than the license text
+const MAX_LICENSE_NAME_LEN: usize = 64;
+
+/// The licenses a distribution declares in its `METADATA`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Licenses {
+    /// SPDX expression from `License-Expression` (PEP 639)
+    pub(crate) expression: Option<String>,
+    /// The `License` field, when it is a short name rather than full text
+    pub(crate) license: Option<String>,
+    /// Names from `License ::` classifiers, e.g. `MIT License`
+    pub(crate) classifiers: Vec<String>,
+}
+
+impl Licenses {
+    /// Read the licenses from a `.dist-info` directory
+    pub(crate) fn read(dist_info: &Path) -> Self {
+        fs::read_to_string(dist_info.join("METADATA"))
+            .map(|metadata| Self::parse(&metadata))
+            .unwrap_or_default()
+    }
+
+    fn parse(metadata: &str) -> Self {
+        let mut licenses = Self::default();
+        let mut in_license = false;
+        let mut license_is_text = false;
+
+        // Headers end at the first blank line, where the description starts
+        for line in metadata.lines().take_while(|line| !line.trim().is_empty()) {
+            // Continuation lines of a multi-line field start with whitespace
+            if line.starts_with([' ', '\t']) {
+                license_is_text |= in_license;
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            let key = key.trim().to_ascii_lowercase();
+            in_license = key == "license";
+
+            match key.as_str() {
+                "license-expression" if !value.is_empty() => {
+                    licenses.expression = Some(value.to_string());
+                }
+                "license" => licenses.license = Some(value.to_string()),
+                "classifier" => {
+                    if let Some(name) = value
+                        .strip_prefix("License ::")
+                        .and_then(|rest| rest.rsplit("::").next())
+                        .map(str::trim)
+                        .filter(|name| *name != "OSI Approved")
+                    {
+                        licenses.classifiers.push(name.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        licenses.license = licenses.license.filter(|license| {
+            !license_is_text
+                && !license.is_empty()
+                && license.len() <= MAX_LICENSE_NAME_LEN
+                && !license.eq_ignore_ascii_case("unknown")
+        });
+        licenses
+    }
+
+    /// The license to group by: the expression, then the field, then classifiers
+    pub(crate) fn name(&self) -> Option<String> {
+        self.expression
+            .clone()
+            .or_else(|| self.license.clone())
+            .or_else(|| (!self.classifiers.is_empty()).then(|| self.classifiers.join(" OR ")))
+    }
+
+    /// The first declared license matching a deny rule
+    ///
+    /// A rule matches license tokens that start with it, ignoring case, so
+    /// `GPL` matches `GPL-3.0-only` and `GPLv3` but not `LGPL-2.1`.
+    pub(crate) fn denied_by<'a>(&self, rules: &'a [String]) -> Option<&'a str> {
+        let declared = self
+            .expression
+            .iter()
+            .chain(&self.license)
+            .chain(&self.classifiers);
+        let tokens: Vec<String> = declared
+            .flat_map(|license| {
+                license
+                    .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+')))
+                    .filter(|token| !token.is_empty())
+                    .map(str::to_ascii_lowercase)
+            })
+            .collect();
+
+        rules.iter().map(String::as_str).find(|rule| {
+            let rule = rule.to_ascii_lowercase();
+            !rule.is_empty() && tokens.iter().any(|token| token.starts_with(&rule))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_licenses() {
+        let licenses = Licenses::parse(
+            "Metadata-Version: 2.4\n\
+             Name: demo\n\
+             License-Expression: MIT OR Apache-2.0\n\
+             Classifier: License :: OSI Approved :: MIT License\n\
+             Classifier: License :: OSI Approved\n\
+             Classifier: Programming Language :: Python\n\
+             \n\
+             License: not a header\n",
+        );
+        assert_eq!(licenses.expression.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(licenses.license, None);
+        assert_eq!(licenses.classifiers, ["MIT License"]);
+        assert_eq!(licenses.name().as_deref(), Some("MIT OR Apache-2.0"));
+    }
+
+    #[test]
+    fn test_full_license_text_is_not_a_name() {
+        let licenses = Licenses::parse(
+            "Name: demo\n\
+             License: Copyright (c) 2024 Someone\n        \
+             Permission is hereby granted...\n\
+             Classifier: License :: OSI Approved :: BSD License\n",
+        );
+        assert_eq!(licenses.license, None);
+        assert_eq!(licenses.name().as_deref(), Some("BSD License"));
+
+        assert_eq!(Licenses::parse("License: UNKNOWN\n").name(), None);
+    }
+
+    #[test]
+    fn test_denied_by() {
+        let rules = vec!["GPL".to_string()];
+        let gpl = Licenses {
+            classifiers: vec!["GNU General Public License v3 (GPLv3)".to_string()],
+            ..Licenses::default()
+        };
+        let lgpl = Licenses {
+            expression: Some("LGPL-2.1-or-later".to_string()),
+            ..Licenses::default()
+        };
+
+        assert_eq!(gpl.denied_by(&rules), Some("GPL"));
+        assert_eq!(lgpl.denied_by(&rules), None);
+    }
+}
@@ -1 +1,15 @@
+pub(crate) mod hooks;
+pub(crate) mod index;
+pub(crate) mod journal;
+pub(crate) mod lockfile;
+pub(crate) mod metadata;
+pub(crate) mod osv;
 pub(crate) mod paths;
+pub(crate) mod pep440;
+pub(crate) mod pyproject;
+pub(crate) mod requirement;
+pub(crate) mod snapshots;
+pub(crate) mod stack;
+pub(crate) mod undo;
+pub(crate) mod vars;
+pub(crate) mod venv;
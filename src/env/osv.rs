@@ -0,0 +1,371 @@
+//! Local database of OSV advisories
+//!
+//! Reads a directory of OSV JSON records, such as an extracted
+//! `PyPI/all.zip` export from osv.dev, and matches Python packages against
+//! their affected ranges.
+
+use crate::env::lockfile::normalize_name;
+use crate::env::pep440::Version;
+use crate::error::{Result, UvupError};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// An advisory affecting at least one Python package
+#[derive(Debug)]
+pub(crate) struct Advisory {
+    pub(crate) id: String,
+    pub(crate) aliases: Vec<String>,
+    pub(crate) summary: Option<String>,
+    /// Qualitative severity such as `HIGH`, when the record has one
+    pub(crate) severity: Option<String>,
+    /// CVSS vectors from the record's `severity` field
+    pub(crate) cvss: Vec<String>,
+    affected: Vec<Affected>,
+}
+
+/// The versions of one package an advisory applies to
+#[derive(Debug)]
+struct Affected {
+    package: String,
+    versions: Vec<String>,
+    ranges: Vec<Vec<Event>>,
+}
+
+#[derive(Debug)]
+enum Event {
+    Introduced(Version),
+    Fixed(Version),
+    LastAffected(Version),
+}
+
+/// An advisory that applies to a package version
+#[derive(Debug)]
+pub(crate) struct Finding<'a> {
+    pub(crate) advisory: &'a Advisory,
+    /// Versions that fix the advisory, from the ranges containing the version
+    pub(crate) fixed: Vec<String>,
+}
+
+/// Advisories indexed by normalized package name
+#[derive(Debug, Default)]
+pub(crate) struct Database {
+    advisories: Vec<Advisory>,
+    by_package: HashMap<String, Vec<usize>>,
+    /// `*.json` files that could not be read or are not OSV records
+    pub(crate) skipped: usize,
+}
+
+impl Database {
+    /// Load every `*.json` record under a directory
+    ///
+    /// Records without Python entries are left out, and files that are not OSV
+    /// records are counted in `skipped`. Fails when no advisory loads, so a
+    /// wrong directory cannot pass an audit.
+    pub(crate) fn load(dir: &Path) -> Result<Self> {
+        if !dir.is_dir() {
+            return Err(UvupError::PathError(format!(
+                "Advisory database not found: {}",
+                dir.display()
+            )));
+        }
+
+        let mut db = Self::default();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "json") {
+                    let Some(record) = fs::read_to_string(&path)
+                        .ok()
+                        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+                        .filter(|record| record["id"].is_string() && record["affected"].is_array())
+                    else {
+                        db.skipped += 1;
+                        continue;
+                    };
+                    if let Some(advisory) = Advisory::parse(&record) {
+                        db.insert(advisory);
+                    }
+                }
+            }
+        }
+
+        if db.advisories.is_empty() {
+            return Err(UvupError::AdvisoryDatabaseEmpty(dir.display().to_string()));
+        }
+        Ok(db)
+    }
+
+    fn insert(&mut self, advisory: Advisory) {
+        let mut packages: Vec<&str> = advisory
+            .affected
+            .iter()
+            .map(|affected| affected.package.as_str())
+            .collect();
+        packages.sort_unstable();
+        packages.dedup();
+
+        for package in packages {
+            self.by_package
+                .entry(package.to_string())
+                .or_default()
+                .push(self.advisories.len());
+        }
+        self.advisories.push(advisory);
+    }
+
+    /// Advisories that apply to a version of a package, sorted by id
+    pub(crate) fn findings(&self, package: &str, version: &Version) -> Vec<Finding<'_>> {
+        let package = normalize_name(package);
+        let mut findings: Vec<Finding<'_>> = self
+            .by_package
+            .get(&package)
+            .into_iter()
+            .flatten()
+            .filter_map(|&index| self.advisories[index].finding(&package, version))
+            .collect();
+        findings.sort_by(|a, b| a.advisory.id.cmp(&b.advisory.id));
+        findings
+    }
+}
+
+impl Advisory {
+    fn parse(record: &Value) -> Option<Self> {
+        let strings = |value: &Value| -> Vec<String> {
+            value
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|s| s.as_str().map(str::to_string))
+                .collect()
+        };
+
+        let affected: Vec<Affected> = record["affected"]
+            .as_array()?
+            .iter()
+            .filter(|affected| affected["package"]["ecosystem"] == "PyPI")
+            .filter_map(|affected| {
+                Some(Affected {
+                    package: normalize_name(affected["package"]["name"].as_str()?),
+                    versions: strings(&affected["versions"]),
+                    ranges: affected["ranges"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter(|range| range["type"] != "GIT")
+                        .map(|range| parse_events(&range["events"]))
+                        .collect(),
+                })
+            })
+            .collect();
+        if affected.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            id: record["id"].as_str()?.to_string(),
+            aliases: strings(&record["aliases"]),
+            summary: record["summary"].as_str().map(str::to_string),
+            severity: record["database_specific"]["severity"]
+                .as_str()
+                .map(str::to_uppercase),
+            cvss: record["severity"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|severity| severity["score"].as_str().map(str::to_string))
+                .collect(),
+            affected,
+        })
+    }
+
+    /// Whether the advisory is known by an id, either its own or an alias
+    pub(crate) fn is_known_as(&self, id: &str) -> bool {
+        self.id.eq_ignore_ascii_case(id) || self.aliases.iter().any(|a| a.eq_ignore_ascii_case(id))
+    }
+
+    fn finding(&self, package: &str, version: &Version) -> Option<Finding<'_>> {
+        let mut affected = false;
+        let mut fixed = Vec::new();
+
+        for entry in self.affected.iter().filter(|a| a.package == package) {
+            if entry
+                .versions
+                .iter()
+                .any(|v| Version::parse(v).is_some_and(|v| v == *version))
+            {
+                affected = true;
+            }
+            for events in &entry.ranges {
+                if range_contains(events, version) {
+                    affected = true;
+                    fixed.extend(events.iter().filter_map(|event| match event {
+                        Event::Fixed(fix) if fix > version => Some(fix.to_string()),
+                        _ => None,
+                    }));
+                }
+            }
+        }
+
+        fixed.sort_by(|a, b| Version::parse(a).cmp(&Version::parse(b)));
+        fixed.dedup();
+        affected.then_some(Finding {
+            advisory: self,
+            fixed,
+        })
+    }
+}
+
+/// Events of a range, sorted by version
+fn parse_events(events: &Value) -> Vec<Event> {
+    let mut events: Vec<Event> = events
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|event| {
+            let version = |key: &str| event[key].as_str().and_then(Version::parse);
+            if let Some(v) = version("introduced") {
+                Some(Event::Introduced(v))
+            } else if let Some(v) = version("fixed") {
+                Some(Event::Fixed(v))
+            } else {
+                version("last_affected").map(Event::LastAffected)
+            }
+        })
+        .collect();
+    events.sort_by(|a, b| a.version().cmp(b.version()));
+    events
+}
+
+impl Event {
+    fn version(&self) -> &Version {
+        match self {
+            Event::Introduced(v) | Event::Fixed(v) | Event::LastAffected(v) => v,
+        }
+    }
+}
+
+/// Evaluate a range as OSV specifies: replay the events up to the version
+fn range_contains(events: &[Event], version: &Version) -> bool {
+    let mut affected = false;
+    for event in events {
+        match event {
+            Event::Introduced(v) if v <= version => affected = true,
+            Event::Fixed(v) if v <= version => affected = false,
+            Event::LastAffected(v) if v < version => affected = false,
+            _ => break,
+        }
+    }
+    affected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn version(text: &str) -> Version {
+        Version::parse(text).unwrap()
+    }
+
+    fn database() -> Database {
+        let mut db = Database::default();
+        for record in [
+            json!({
+                "id": "GHSA-h5c8-rqwp-cp95",
+                "aliases": ["CVE-2024-22195"],
+                "summary": "Jinja vulnerable to HTML attribute injection",
+                "database_specific": { "severity": "MODERATE" },
+                "affected": [{
+                    "package": { "ecosystem": "PyPI", "name": "Jinja2" },
+                    "ranges": [{
+                        "type": "ECOSYSTEM",
+                        "events": [{ "introduced": "0" }, { "fixed": "3.1.3" }]
+                    }]
+                }]
+            }),
+            json!({
+                "id": "PYSEC-2099-1",
+                "affected": [{
+                    "package": { "ecosystem": "PyPI", "name": "jinja2" },
+                    "ranges": [{
+                        "type": "ECOSYSTEM",
+                        "events": [
+                            { "introduced": "2.0" }, { "fixed": "2.11.3" },
+                            { "introduced": "3.0.0" }, { "last_affected": "3.0.1" }
+                        ]
+                    }],
+                    "versions": ["1.2"]
+                }]
+            }),
+            json!({
+                "id": "RUSTSEC-2099-1",
+                "affected": [{ "package": { "ecosystem": "crates.io", "name": "jinja2" } }]
+            }),
+        ] {
+            if let Some(advisory) = Advisory::parse(&record) {
+                db.insert(advisory);
+            }
+        }
+        db
+    }
+
+    fn ids(db: &Database, v: &str) -> Vec<String> {
+        db.findings("jinja2", &version(v))
+            .iter()
+            .map(|finding| finding.advisory.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_findings() {
+        let db = database();
+
+        assert_eq!(ids(&db, "1.2"), ["GHSA-h5c8-rqwp-cp95", "PYSEC-2099-1"]);
+        assert_eq!(ids(&db, "2.11.3"), ["GHSA-h5c8-rqwp-cp95"]);
+        assert_eq!(ids(&db, "3.0.1"), ["GHSA-h5c8-rqwp-cp95", "PYSEC-2099-1"]);
+        assert_eq!(ids(&db, "3.0.2"), ["GHSA-h5c8-rqwp-cp95"]);
+        assert!(ids(&db, "3.1.3").is_empty());
+
+        let findings = db.findings("Jinja2", &version("2.10"));
+        assert_eq!(findings[0].fixed, ["3.1.3"]);
+        assert_eq!(findings[1].fixed, ["2.11.3"]);
+        assert_eq!(findings[0].advisory.severity.as_deref(), Some("MODERATE"));
+    }
+
+    #[test]
+    fn test_load_requires_python_advisories() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            Database::load(dir.path()),
+            Err(UvupError::AdvisoryDatabaseEmpty(_))
+        ));
+
+        fs::write(dir.path().join("broken.json"), "{").unwrap();
+        fs::write(dir.path().join("notes.json"), "{\"title\": \"not OSV\"}").unwrap();
+        fs::write(
+            dir.path().join("PYSEC-2099-2.json"),
+            json!({
+                "id": "PYSEC-2099-2",
+                "affected": [{ "package": { "ecosystem": "PyPI", "name": "demo" } }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let db = Database::load(dir.path()).unwrap();
+        assert_eq!(db.advisories.len(), 1);
+        assert_eq!(db.skipped, 2);
+    }
+
+    #[test]
+    fn test_is_known_as() {
+        let db = database();
+        let findings = db.findings("jinja2", &version("3.1.2"));
+        assert!(findings[0].advisory.is_known_as("cve-2024-22195"));
+        assert!(!findings[0].advisory.is_known_as("PYSEC-2099-1"));
+    }
+}
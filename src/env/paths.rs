@@ -1,5 +1,8 @@
 use crate::error::{Result, UvupError};
-use std::path::PathBuf;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 pub(crate) fn get_home_dir() -> Result<PathBuf> {
     dirs::home_dir()
@@ -10,12 +13,121 @@ pub(crate) fn get_envs_dir() -> Result<PathBuf> {
     Ok(get_home_dir()?.join(".uvup"))
 }
 
+/// Get the directory holding generated shell hook files
+pub(crate) fn get_shell_dir() -> Result<PathBuf> {
+    Ok(get_envs_dir()?.join(".shell"))
+}
+
 /// Get the project directory path for an environment (contains pyproject.toml)
 pub(crate) fn get_env_path(name: &str) -> Result<PathBuf> {
     validate_env_name(name)?;
     Ok(get_envs_dir()?.join(name))
 }
 
+/// Get the directory holding uvup's own metadata for an environment
+pub(crate) fn get_env_meta_dir(name: &str) -> Result<PathBuf> {
+    Ok(get_env_path(name)?.join(".uvup"))
+}
+
+/// Get the virtual environment directory of an environment
+pub(crate) fn get_venv_path(name: &str) -> Result<PathBuf> {
+    Ok(get_env_path(name)?.join(".venv"))
+}
+
+/// Get the environment name owning a venv, if the venv is a uvup environment
+pub(crate) fn env_name_for_venv(venv_path: &Path) -> Option<String> {
+    if venv_path.file_name()? != ".venv" {
+        return None;
+    }
+    env_name_for_project(venv_path.parent()?)
+}
+
+/// Get the environment name of a project directory, if it is a uvup environment
+pub(crate) fn env_name_for_project(project_dir: &Path) -> Option<String> {
+    if project_dir.parent()? != get_envs_dir().ok()? {
+        return None;
+    }
+    project_dir.file_name()?.to_str().map(str::to_string)
+}
+
+/// Get the directory holding the venv's executables and activation scripts
+pub(crate) fn get_venv_bin_dir(venv_path: &Path) -> PathBuf {
+    if cfg!(target_os = "windows") {
+        venv_path.join("Scripts")
+    } else {
+        venv_path.join("bin")
+    }
+}
+
+/// Fail with `EnvNotFound` unless the environment exists
+pub(crate) fn ensure_env_exists(name: &str) -> Result<()> {
+    if !get_env_path(name)?.exists() {
+        return Err(UvupError::EnvNotFound(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Resolve the environment a package command operates on
+///
+/// A name given with `--env` wins, then `UVUP_ENV`, then the active environment.
+/// `UVUP_ENV` overriding a different active environment is announced on stderr.
+pub(crate) fn resolve_env(name: Option<String>) -> Result<String> {
+    let active = env::var("UVUP_ACTIVE_ENV").ok();
+    let name = match (name, env::var("UVUP_ENV").ok().filter(|n| !n.is_empty())) {
+        (Some(name), _) => name,
+        (None, Some(name)) => {
+            if let Some(active) = active.filter(|active| *active != name) {
+                let _ = writeln!(
+                    io::stderr(),
+                    "Note: Using '{name}' from UVUP_ENV instead of the active environment '{active}'"
+                );
+            }
+            name
+        }
+        (None, None) => active.ok_or(UvupError::NoActiveEnvironment)?,
+    };
+    ensure_env_exists(&name)?;
+    Ok(name)
+}
+
+/// List the names of all valid environments, sorted
+pub(crate) fn list_env_names() -> Result<Vec<String>> {
+    let envs_dir = get_envs_dir()?;
+    let mut env_names = Vec::new();
+
+    if !envs_dir.exists() {
+        return Ok(env_names);
+    }
+
+    for entry in fs::read_dir(&envs_dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() && is_valid_env(&path) {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                env_names.push(name.to_string());
+            }
+        }
+    }
+
+    env_names.sort();
+    Ok(env_names)
+}
+
+fn is_valid_env(path: &Path) -> bool {
+    // Check for .venv subdirectory structure (new format)
+    let bin_dir = get_venv_bin_dir(&path.join(".venv"));
+
+    #[cfg(target_os = "windows")]
+    {
+        bin_dir.join("Activate.ps1").exists()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        bin_dir.join("activate").exists()
+    }
+}
+
 pub(crate) fn validate_env_name(name: &str) -> Result<()> {
     if name.is_empty() {
         return Err(UvupError::InvalidEnvName(
@@ -50,4 +162,16 @@ mod tests {
         assert!(validate_env_name("my/project").is_err());
         assert!(validate_env_name("my.project").is_err());
     }
+
+    #[test]
+    fn test_env_name_for_venv() {
+        let venv = get_venv_path("myproject").unwrap();
+        assert_eq!(env_name_for_venv(&venv), Some("myproject".to_string()));
+
+        assert_eq!(env_name_for_venv(Path::new("/work/project/.venv")), None);
+        assert_eq!(
+            env_name_for_venv(&get_env_path("myproject").unwrap().join("venv")),
+            None
+        );
+    }
 }
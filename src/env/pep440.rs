@@ -0,0 +1,455 @@
+//! PEP 440 versions and version specifiers
+//!
+//! Enough of the specification to compare resolved versions and match them
+//! against specifiers such as `>=2,<3`, `~=1.4` or `==2.*`.
+
+use crate::error::{Result, UvupError};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A parsed PEP 440 version
+#[derive(Debug, Clone)]
+pub(crate) struct Version {
+    epoch: u64,
+    release: Vec<u64>,
+    pre: Option<(PreKind, u64)>,
+    post: Option<u64>,
+    dev: Option<u64>,
+    local: Vec<LocalSegment>,
+    /// The version as written, for display and `===`
+    text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum PreKind {
+    Alpha,
+    Beta,
+    Rc,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum LocalSegment {
+    // Strings sort before numbers
+    Text(String),
+    Number(u64),
+}
+
+impl Version {
+    /// Parse a version, accepting the normalizations PEP 440 allows
+    pub(crate) fn parse(text: &str) -> Option<Self> {
+        let original = text.trim();
+        let lower = original.to_lowercase();
+        let mut rest = lower.strip_prefix('v').unwrap_or(&lower);
+
+        let (public, local) = match rest.split_once('+') {
+            Some((public, local)) => (public, Some(local)),
+            None => (rest, None),
+        };
+        rest = public;
+
+        let epoch = match rest.split_once('!') {
+            Some((epoch, release)) => {
+                rest = release;
+                epoch.parse().ok()?
+            }
+            None => 0,
+        };
+
+        let release_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let release_text = rest[..release_end].trim_end_matches('.');
+        let release: Vec<u64> = release_text
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect::<Option<_>>()?;
+        rest = &rest[release_text.len()..];
+
+        let pre = parse_suffix(
+            &mut rest,
+            &["alpha", "a", "beta", "b", "preview", "pre", "rc", "c"],
+        )
+        .map(|(label, number)| {
+            let kind = match label {
+                "alpha" | "a" => PreKind::Alpha,
+                "beta" | "b" => PreKind::Beta,
+                _ => PreKind::Rc,
+            };
+            (kind, number)
+        });
+
+        // `1.0-1` is an implicit post-release
+        let post = match implicit_post(&mut rest) {
+            Some(number) => Some(number),
+            None => parse_suffix(&mut rest, &["post", "rev", "r"]).map(|(_, number)| number),
+        };
+
+        let dev = parse_suffix(&mut rest, &["dev"]).map(|(_, number)| number);
+
+        if !rest.is_empty() {
+            return None;
+        }
+
+        let local = match local {
+            Some(local) => {
+                let segments: Vec<LocalSegment> = local
+                    .split(['.', '-', '_'])
+                    .map(|segment| match segment.parse() {
+                        Ok(number) => LocalSegment::Number(number),
+                        Err(_) => LocalSegment::Text(segment.to_string()),
+                    })
+                    .collect();
+                let valid = segments.iter().all(|segment| match segment {
+                    LocalSegment::Text(text) => {
+                        !text.is_empty() && text.chars().all(|c| c.is_ascii_alphanumeric())
+                    }
+                    LocalSegment::Number(_) => true,
+                });
+                if !valid {
+                    return None;
+                }
+                segments
+            }
+            None => Vec::new(),
+        };
+
+        Some(Self {
+            epoch,
+            release,
+            pre,
+            post,
+            dev,
+            local,
+            text: original.to_string(),
+        })
+    }
+
+    /// Whether this is a pre-release or development release
+    pub(crate) fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    /// The same version without its local segment
+    fn public(&self) -> Self {
+        Self {
+            local: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Release segment at `index`, padding with zeros
+    fn release_at(&self, index: usize) -> u64 {
+        self.release.get(index).copied().unwrap_or(0)
+    }
+
+    fn same_release(&self, other: &Self) -> bool {
+        let len = self.release.len().max(other.release.len());
+        self.epoch == other.epoch && (0..len).all(|i| self.release_at(i) == other.release_at(i))
+    }
+
+    /// Sort key of the pre/post/dev segments, following PEP 440's ordering
+    fn suffix_key(&self) -> (i8, PreKind, u64, i8, u64, i8, u64) {
+        let (pre_rank, pre_kind, pre_number) = match (self.pre, self.post, self.dev) {
+            (Some((kind, number)), _, _) => (0, kind, number),
+            // A bare dev release sorts before all pre-releases of its version
+            (None, None, Some(_)) => (-1, PreKind::Alpha, 0),
+            (None, _, _) => (1, PreKind::Alpha, 0),
+        };
+        let (post_rank, post_number) = self.post.map_or((-1, 0), |number| (0, number));
+        let (dev_rank, dev_number) = self.dev.map_or((1, 0), |number| (0, number));
+        (
+            pre_rank,
+            pre_kind,
+            pre_number,
+            post_rank,
+            post_number,
+            dev_rank,
+            dev_number,
+        )
+    }
+}
+
+/// Parse an optional `-number` post-release suffix
+fn implicit_post(rest: &mut &str) -> Option<u64> {
+    let digits = rest.strip_prefix('-')?;
+    let end = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    let number = digits[..end].parse().ok()?;
+    *rest = &digits[end..];
+    Some(number)
+}
+
+/// Parse an optional `[.-_]label[.-_]number` suffix, returning the label and number
+fn parse_suffix<'a>(rest: &mut &str, labels: &[&'a str]) -> Option<(&'a str, u64)> {
+    let trimmed = rest.trim_start_matches(['.', '-', '_']);
+    let label = labels.iter().find(|label| trimmed.starts_with(**label))?;
+    let after = trimmed[label.len()..].trim_start_matches(['.', '-', '_']);
+    let end = after
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(after.len());
+    let number = if end == 0 {
+        0
+    } else {
+        after[..end].parse().ok()?
+    };
+    *rest = &after[end..];
+    Some((label, number))
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.release.len().max(other.release.len());
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| {
+                (0..len)
+                    .map(|i| self.release_at(i).cmp(&other.release_at(i)))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .then_with(|| self.suffix_key().cmp(&other.suffix_key()))
+            .then_with(|| self.local.cmp(&other.local))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Compatible,
+    Equal,
+    EqualPrefix,
+    NotEqual,
+    NotEqualPrefix,
+    LessEqual,
+    GreaterEqual,
+    Less,
+    Greater,
+}
+
+/// A single clause such as `>=2.0`
+#[derive(Debug, Clone)]
+enum Specifier {
+    Compare(Operator, Version),
+    /// `===`, comparing the version text as is
+    Arbitrary(String),
+}
+
+/// A comma-separated set of specifiers, all of which must match
+#[derive(Debug, Clone)]
+pub(crate) struct VersionSpecifiers {
+    specifiers: Vec<Specifier>,
+}
+
+impl VersionSpecifiers {
+    /// Parse specifiers such as `>=2,<3`; a bare version means `==`
+    pub(crate) fn parse(text: &str) -> Result<Self> {
+        let specifiers = text
+            .split(',')
+            .map(|clause| {
+                Specifier::parse(clause.trim())
+                    .ok_or_else(|| UvupError::InvalidVersionSpec(text.to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { specifiers })
+    }
+
+    /// Whether a version satisfies every specifier
+    pub(crate) fn contains(&self, version: &Version) -> bool {
+        self.specifiers
+            .iter()
+            .all(|specifier| specifier.contains(version))
+    }
+}
+
+impl Specifier {
+    fn parse(clause: &str) -> Option<Self> {
+        const OPERATORS: [(&str, Operator); 7] = [
+            ("~=", Operator::Compatible),
+            ("==", Operator::Equal),
+            ("!=", Operator::NotEqual),
+            ("<=", Operator::LessEqual),
+            (">=", Operator::GreaterEqual),
+            ("<", Operator::Less),
+            (">", Operator::Greater),
+        ];
+
+        if let Some(text) = clause.strip_prefix("===") {
+            let text = text.trim();
+            return (!text.is_empty()).then(|| Specifier::Arbitrary(text.to_string()));
+        }
+
+        let (mut operator, rest) = OPERATORS
+            .iter()
+            .find_map(|(prefix, operator)| Some((*operator, clause.strip_prefix(prefix)?)))
+            .unwrap_or((Operator::Equal, clause));
+        let mut rest = rest.trim();
+
+        if let Some(prefix) = rest.strip_suffix(".*") {
+            operator = match operator {
+                Operator::Equal => Operator::EqualPrefix,
+                Operator::NotEqual => Operator::NotEqualPrefix,
+                _ => return None,
+            };
+            rest = prefix;
+        }
+
+        let version = Version::parse(rest)?;
+        if operator == Operator::Compatible && version.release.len() < 2 {
+            return None;
+        }
+
+        Some(Specifier::Compare(operator, version))
+    }
+
+    fn contains(&self, candidate: &Version) -> bool {
+        let (operator, spec) = match self {
+            Specifier::Arbitrary(text) => return candidate.text.eq_ignore_ascii_case(text),
+            Specifier::Compare(operator, spec) => (*operator, spec),
+        };
+
+        match operator {
+            Operator::Equal => equals(candidate, spec),
+            Operator::NotEqual => !equals(candidate, spec),
+            Operator::EqualPrefix => prefix_matches(candidate, spec),
+            Operator::NotEqualPrefix => !prefix_matches(candidate, spec),
+            Operator::LessEqual => candidate.public() <= *spec,
+            Operator::GreaterEqual => candidate.public() >= *spec,
+            Operator::Less => {
+                // `<3` excludes pre-releases of 3 itself
+                candidate < spec
+                    && (spec.is_prerelease()
+                        || !candidate.is_prerelease()
+                        || !candidate.same_release(spec))
+            }
+            Operator::Greater => {
+                // `>3` excludes post-releases and local versions of 3 itself
+                candidate > spec
+                    && (spec.post.is_some()
+                        || candidate.post.is_none()
+                        || !candidate.same_release(spec))
+                    && candidate.public() != *spec
+            }
+            Operator::Compatible => {
+                let mut prefix = spec.clone();
+                prefix.release.pop();
+                candidate.public() >= *spec && prefix_matches(candidate, &prefix)
+            }
+        }
+    }
+}
+
+/// `==` ignores the candidate's local segment unless the specifier has one
+fn equals(candidate: &Version, spec: &Version) -> bool {
+    if spec.local.is_empty() {
+        candidate.public() == *spec
+    } else {
+        candidate == spec
+    }
+}
+
+/// Whether the release segment starts with the prefix, as in `==1.4.*`
+fn prefix_matches(candidate: &Version, prefix: &Version) -> bool {
+    candidate.epoch == prefix.epoch
+        && prefix
+            .release
+            .iter()
+            .enumerate()
+            .all(|(i, segment)| candidate.release_at(i) == *segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(text: &str) -> Version {
+        Version::parse(text).unwrap()
+    }
+
+    fn matches(spec: &str, version: &str) -> bool {
+        VersionSpecifiers::parse(spec)
+            .unwrap()
+            .contains(&v(version))
+    }
+
+    #[test]
+    fn test_parse_and_normalize() {
+        assert_eq!(v("1.0"), v("1.0.0"));
+        assert_eq!(v("1.0a1"), v("1.0.0-alpha.1"));
+        assert_eq!(v("1.0.post2"), v("1.0-2"));
+        assert_eq!(v("1.0rc"), v("1.0c0"));
+        assert_eq!(v("v2!1.0"), v("2!1.0"));
+        assert!(Version::parse("not-a-version").is_none());
+        assert!(Version::parse("1.0+").is_none());
+        assert!(v("2.0.dev1").is_prerelease());
+    }
+
+    #[test]
+    fn test_ordering() {
+        let ordered = [
+            "1.0.dev0",
+            "1.0a1.dev1",
+            "1.0a1",
+            "1.0b2",
+            "1.0rc1",
+            "1.0",
+            "1.0+local.1",
+            "1.0.post1.dev0",
+            "1.0.post1",
+            "1.1",
+            "1!0.1",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_specifiers() {
+        assert!(matches(">=2,<3", "2.3.1"));
+        assert!(!matches(">=2,<3", "3.0"));
+        assert!(!matches("<3", "3.0rc1"));
+        assert!(matches("<3rc2", "3.0rc1"));
+        assert!(!matches(">2", "2.0.post1"));
+        assert!(!matches(">2", "2.0+cu121"));
+        assert!(matches(">2", "2.0.1"));
+        assert!(matches("==2.*", "2.4.0"));
+        assert!(!matches("==2.*", "20.0"));
+        assert!(matches("!=2.1.*", "2.2"));
+        assert!(matches("~=1.4.2", "1.4.9"));
+        assert!(!matches("~=1.4.2", "1.5.0"));
+        assert!(matches("~=1.4", "1.9"));
+        assert!(matches("==2.1.0", "2.1.0+cu121"));
+        assert!(!matches("==2.1.0+cpu", "2.1.0+cu121"));
+        assert!(matches("2.1", "2.1.0"));
+        assert!(matches("===1.0.0", "1.0.0"));
+        assert!(!matches("===1.0", "1.0.0"));
+    }
+
+    #[test]
+    fn test_invalid_specifiers() {
+        assert!(VersionSpecifiers::parse(">=").is_err());
+        assert!(VersionSpecifiers::parse("~=1").is_err());
+        assert!(VersionSpecifiers::parse(">=2.*").is_err());
+    }
+}
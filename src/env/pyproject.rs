@@ -0,0 +1,242 @@
+//! Shared reading, filtering and comparing of `pyproject.toml` dependencies
+//!
+//! Used by `uvup new` and `uvup sync` to derive a project from a template.
+
+// Allow println! in this module as it's used for user-facing output
+#![allow(clippy::print_stdout)]
+
+use crate::error::{Result, UvupError};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use toml_edit::{Array, DocumentMut, Item, Table, Value};
+
+/// Read and parse pyproject.toml
+pub(crate) fn read_and_parse_toml(path: &Path) -> Result<DocumentMut> {
+    let toml_content = fs::read_to_string(path)
+        .map_err(|e| UvupError::PathError(format!("Failed to read pyproject.toml: {e}")))?;
+
+    toml_content.parse::<DocumentMut>().map_err(|e| {
+        UvupError::CommandExecutionFailed(format!("Failed to parse pyproject.toml: {e}"))
+    })
+}
+
+/// Get Python version from pyproject.toml
+pub(crate) fn get_python_version_from_toml(doc: &DocumentMut) -> Result<String> {
+    let version_str = doc
+        .get("project")
+        .and_then(|p| p.get("requires-python"))
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| {
+            UvupError::CommandExecutionFailed(
+                "No requires-python found in pyproject.toml".to_string(),
+            )
+        })?;
+
+    let version = version_str
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .split('.')
+        .take(2)
+        .collect::<Vec<_>>()
+        .join(".");
+
+    Ok(version)
+}
+
+/// Update Python version in pyproject.toml
+pub(crate) fn update_python_version(doc: &mut DocumentMut, version: &str) -> Result<()> {
+    let requires_python = doc
+        .get_mut("project")
+        .and_then(|p| p.get_mut("requires-python"))
+        .ok_or_else(|| {
+            UvupError::CommandExecutionFailed(
+                "No requires-python found in pyproject.toml".to_string(),
+            )
+        })?;
+
+    *requires_python = Item::Value(Value::from(format!(">={version}")));
+    Ok(())
+}
+
+/// Filter a single dependency array
+pub(crate) fn filter_dependency_array(
+    deps_array: &Array,
+    exclude: Option<&[String]>,
+    include: Option<&[String]>,
+) -> Vec<Value> {
+    let mut filtered_deps = Vec::new();
+
+    for dep in deps_array {
+        // `{ include-group = "..." }` entries of dependency groups are kept as-is
+        let Some(dep_str) = dep.as_str() else {
+            filtered_deps.push(dep.clone());
+            continue;
+        };
+        let package_name = extract_package_name(dep_str);
+
+        if let Some(include_list) = include {
+            let included = include_list
+                .iter()
+                .any(|inc| package_name == inc.to_lowercase());
+            if !included {
+                continue;
+            }
+        }
+
+        if let Some(exclude_list) = exclude {
+            let excluded = exclude_list
+                .iter()
+                .any(|exc| package_name == exc.to_lowercase());
+            if excluded {
+                println!("  Excluding: {package_name}");
+                continue;
+            }
+        }
+
+        filtered_deps.push(dep.clone());
+    }
+
+    filtered_deps
+}
+
+/// Filter each extra of optional-dependencies, removing extras left empty
+pub(crate) fn filter_optional_dependencies(
+    table: &mut Table,
+    exclude: Option<&[String]>,
+    include: Option<&[String]>,
+) {
+    let mut empty_groups = Vec::new();
+
+    for (group_name, group_deps) in table.iter_mut() {
+        if let Some(deps_array) = group_deps.as_array_mut() {
+            let filtered = filter_dependency_array(deps_array, exclude, include);
+
+            if filtered.is_empty() {
+                println!("  Note: Optional group '{group_name}' is now empty after filtering");
+                empty_groups.push(group_name.to_string());
+            } else {
+                *deps_array = Array::from_iter(filtered);
+            }
+        }
+    }
+
+    for group in empty_groups {
+        table.remove(&group);
+    }
+}
+
+/// Filter each group of dependency-groups, keeping empty groups since others may include them
+pub(crate) fn filter_dependency_groups(
+    table: &mut Table,
+    exclude: Option<&[String]>,
+    include: Option<&[String]>,
+) {
+    for (group_name, group_deps) in table.iter_mut() {
+        if let Some(deps_array) = group_deps.as_array_mut() {
+            let filtered = filter_dependency_array(deps_array, exclude, include);
+
+            if filtered.is_empty() {
+                println!("  Note: Dependency group '{group_name}' is now empty after filtering");
+            }
+            *deps_array = Array::from_iter(filtered);
+        }
+    }
+}
+
+/// Extract package name from dependency string
+fn extract_package_name(dep_str: &str) -> String {
+    let end_pos = dep_str
+        .find(['=', '>', '<', '~', '!', '['])
+        .unwrap_or(dep_str.len());
+    dep_str[..end_pos].trim().to_lowercase()
+}
+
+/// Extract dependencies
+pub(crate) fn extract_dependencies(doc: &DocumentMut) -> Vec<String> {
+    doc.get("project")
+        .and_then(|p| p.get("dependencies"))
+        .and_then(|d| d.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Extract optional-dependencies
+pub(crate) fn extract_optional_dependencies(doc: &DocumentMut) -> HashMap<String, Vec<String>> {
+    extract_groups(
+        doc.get("project")
+            .and_then(|p| p.get("optional-dependencies")),
+    )
+}
+
+/// Extract dependency-groups
+pub(crate) fn extract_dependency_groups(doc: &DocumentMut) -> HashMap<String, Vec<String>> {
+    extract_groups(doc.get("dependency-groups"))
+}
+
+/// Extract the entries of each group in a table of dependency arrays
+fn extract_groups(table: Option<&Item>) -> HashMap<String, Vec<String>> {
+    let mut result = HashMap::new();
+
+    if let Some(table) = table.and_then(Item::as_table) {
+        for (key, value) in table {
+            if let Some(arr) = value.as_array() {
+                let deps: Vec<String> = arr
+                    .iter()
+                    .map(|v| v.as_str().map_or_else(|| v.to_string(), String::from))
+                    .collect();
+                result.insert(key.to_string(), deps);
+            }
+        }
+    }
+
+    result
+}
+
+/// Print how the groups of optional-dependencies or dependency-groups changed
+///
+/// `removed` is shown for groups that no longer exist.
+pub(crate) fn compare_groups(
+    title: &str,
+    before: &HashMap<String, Vec<String>>,
+    after: &HashMap<String, Vec<String>>,
+    removed: &str,
+) {
+    if before.is_empty() && after.is_empty() {
+        return;
+    }
+
+    println!("{title}:");
+
+    let mut all_groups: HashSet<String> = before.keys().cloned().collect();
+    all_groups.extend(after.keys().cloned());
+
+    let mut groups: Vec<_> = all_groups.into_iter().collect();
+    groups.sort();
+
+    for group in groups {
+        let before_deps = before.get(&group);
+        let after_deps = after.get(&group);
+
+        match (before_deps, after_deps) {
+            (Some(old), Some(new)) if old == new => {
+                println!("  [{group}]: No changes");
+            }
+            (Some(_), Some(new)) => {
+                println!("  [{group}]: Modified ({} packages)", new.len());
+            }
+            (Some(_), None) => {
+                println!("  [{group}]: {removed}");
+            }
+            (None, Some(new)) => {
+                println!("  [{group}]: Added ({} packages)", new.len());
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+    println!();
+}
@@ -0,0 +1,80 @@
+//! Minimal PEP 508 requirement parsing
+//!
+//! Splits a requirement such as `requests[socks]>=2.28 ; python_version < "3.13"`
+//! into the parts uvup compares, without evaluating markers.
+
+use crate::env::lockfile::normalize_name;
+
+/// A dependency as written in `pyproject.toml`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Requirement {
+    /// Normalized package name
+    pub(crate) name: String,
+    /// Version specifiers, empty when any version is accepted
+    pub(crate) specifier: String,
+    /// Direct reference after `@`, which replaces the specifier
+    pub(crate) url: Option<String>,
+}
+
+impl Requirement {
+    pub(crate) fn parse(requirement: &str) -> Self {
+        let requirement = requirement.trim();
+        let end = requirement
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+            .unwrap_or(requirement.len());
+        let name = normalize_name(&requirement[..end]);
+
+        let mut rest = requirement[end..].trim_start();
+        if rest.starts_with('[') {
+            rest = rest.find(']').map_or("", |close| &rest[close + 1..]);
+        }
+        let rest = rest.split_once(';').map_or(rest, |(spec, _)| spec).trim();
+
+        if let Some(url) = rest.strip_prefix('@') {
+            return Self {
+                name,
+                specifier: String::new(),
+                url: Some(url.trim().to_string()),
+            };
+        }
+
+        let specifier = rest
+            .strip_prefix('(')
+            .and_then(|spec| spec.strip_suffix(')'))
+            .unwrap_or(rest)
+            .split_whitespace()
+            .collect();
+
+        Self {
+            name,
+            specifier,
+            url: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_requirement() {
+        let requirement = Requirement::parse("Typing_Extensions ; python_version < '3.11'");
+        assert_eq!(requirement.name, "typing-extensions");
+        assert_eq!(requirement.specifier, "");
+
+        let requirement = Requirement::parse("uvicorn[standard] >= 0.30, < 1");
+        assert_eq!(requirement.name, "uvicorn");
+        assert_eq!(requirement.specifier, ">=0.30,<1");
+
+        let requirement = Requirement::parse("requests (>=2.28)");
+        assert_eq!(requirement.specifier, ">=2.28");
+
+        let requirement = Requirement::parse("pkg @ git+https://example.com/pkg");
+        assert_eq!(requirement.name, "pkg");
+        assert_eq!(
+            requirement.url.as_deref(),
+            Some("git+https://example.com/pkg")
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use crate::env::paths::{get_env_meta_dir, get_env_path};
+use crate::error::{Result, UvupError};
+use crate::utils::{compact_timestamp, iso_timestamp};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SNAPSHOTS_DIR: &str = "snapshots";
+const SNAPSHOT_META_FILE: &str = "snapshot.json";
+
+/// Project files saved in a snapshot; `uv.lock` may be missing
+const SNAPSHOT_FILES: [&str; 2] = ["pyproject.toml", "uv.lock"];
+
+/// A saved copy of an environment's `pyproject.toml` and `uv.lock`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Snapshot {
+    /// Creation time as a compact timestamp, unique within the environment
+    pub(crate) id: String,
+    pub(crate) label: Option<String>,
+    pub(crate) created: String,
+    pub(crate) path: PathBuf,
+}
+
+fn store_dir(name: &str) -> Result<PathBuf> {
+    Ok(get_env_meta_dir(name)?.join(SNAPSHOTS_DIR))
+}
+
+/// Save the current project files of an environment
+pub(crate) fn create(name: &str, label: Option<&str>) -> Result<Snapshot> {
+    create_in(&store_dir(name)?, &get_env_path(name)?, label)
+}
+
+/// List the snapshots of an environment, oldest first
+pub(crate) fn list(name: &str) -> Result<Vec<Snapshot>> {
+    list_in(&store_dir(name)?)
+}
+
+/// Find a snapshot by id, or the most recent one with the given label
+pub(crate) fn find(name: &str, reference: &str) -> Result<Snapshot> {
+    find_in(list(name)?, reference)
+        .ok_or_else(|| UvupError::SnapshotNotFound(name.to_string(), reference.to_string()))
+}
+
+/// Copy `pyproject.toml` and `uv.lock` (if present) of a project into a directory
+pub(crate) fn save_files(project_dir: &Path, dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    for file in SNAPSHOT_FILES {
+        let source = project_dir.join(file);
+        if source.exists() {
+            fs::copy(&source, dir.join(file))
+                .map_err(|e| UvupError::PathError(format!("Failed to save {file}: {e}")))?;
+        }
+    }
+    Ok(())
+}
+
+/// Copy files saved by [`save_files`] back into a project directory
+///
+/// A lock file is removed if none was saved.
+pub(crate) fn restore_files(dir: &Path, project_dir: &Path) -> Result<()> {
+    for file in SNAPSHOT_FILES {
+        let saved = dir.join(file);
+        let target = project_dir.join(file);
+        if saved.exists() {
+            fs::copy(&saved, &target)
+                .map_err(|e| UvupError::PathError(format!("Failed to restore {file}: {e}")))?;
+        } else if target.exists() {
+            fs::remove_file(&target)?;
+        }
+    }
+    Ok(())
+}
+
+fn create_in(store: &Path, project_dir: &Path, label: Option<&str>) -> Result<Snapshot> {
+    if !project_dir.join("pyproject.toml").exists() {
+        return Err(UvupError::PathError(format!(
+            "No pyproject.toml found in {}",
+            project_dir.display()
+        )));
+    }
+
+    // Snapshots taken within the same second get a numeric suffix
+    let timestamp = compact_timestamp();
+    let mut id = timestamp.clone();
+    let mut suffix = 1;
+    while store.join(&id).exists() {
+        suffix += 1;
+        id = format!("{timestamp}-{suffix}");
+    }
+
+    let path = store.join(&id);
+    save_files(project_dir, &path)?;
+
+    let snapshot = Snapshot {
+        id,
+        label: label.map(str::to_string),
+        created: iso_timestamp(),
+        path,
+    };
+    let meta = json!({ "label": snapshot.label, "created": snapshot.created });
+    fs::write(snapshot.path.join(SNAPSHOT_META_FILE), format!("{meta}\n"))?;
+
+    Ok(snapshot)
+}
+
+fn list_in(store: &Path) -> Result<Vec<Snapshot>> {
+    if !store.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots = Vec::new();
+    for entry in fs::read_dir(store)? {
+        let path = entry?.path();
+        let Some(id) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        let Some(meta) = fs::read_to_string(path.join(SNAPSHOT_META_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        else {
+            continue;
+        };
+
+        snapshots.push(Snapshot {
+            id,
+            label: meta["label"].as_str().map(str::to_string),
+            created: meta["created"].as_str().unwrap_or_default().to_string(),
+            path,
+        });
+    }
+
+    snapshots.sort_by(|a, b| {
+        a.created
+            .cmp(&b.created)
+            .then_with(|| sequence(&a.id).cmp(&sequence(&b.id)))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    Ok(snapshots)
+}
+
+/// Position of a snapshot among those taken in the same second
+///
+/// Ids are `<date>-<time>` with a `-<n>` suffix from the second snapshot on,
+/// so the suffix must be compared as a number for `-10` to sort after `-2`.
+fn sequence(id: &str) -> u32 {
+    id.splitn(3, '-')
+        .nth(2)
+        .and_then(|suffix| suffix.parse().ok())
+        .unwrap_or(1)
+}
+
+fn find_in(snapshots: Vec<Snapshot>, reference: &str) -> Option<Snapshot> {
+    let mut by_label = None;
+    for snapshot in snapshots {
+        if snapshot.id == reference {
+            return Some(snapshot);
+        }
+        if snapshot.label.as_deref() == Some(reference) {
+            by_label = Some(snapshot);
+        }
+    }
+    by_label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_list_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("project");
+        let store = dir.path().join("store");
+        fs::create_dir_all(&project).unwrap();
+
+        assert!(create_in(&store, &project, None).is_err());
+
+        fs::write(project.join("pyproject.toml"), "v1").unwrap();
+        let first = create_in(&store, &project, Some("before-upgrade")).unwrap();
+        assert!(!first.path.join("uv.lock").exists());
+
+        fs::write(project.join("pyproject.toml"), "v2").unwrap();
+        fs::write(project.join("uv.lock"), "lock v2").unwrap();
+        let second = create_in(&store, &project, Some("before-upgrade")).unwrap();
+        assert_ne!(first.id, second.id);
+        assert!(second.path.join("uv.lock").exists());
+
+        let snapshots = list_in(&store).unwrap();
+        assert_eq!(snapshots, vec![first.clone(), second.clone()]);
+
+        // Labels resolve to the most recent snapshot, ids to exactly one
+        assert_eq!(find_in(snapshots.clone(), "before-upgrade"), Some(second));
+        assert_eq!(find_in(snapshots.clone(), &first.id), Some(first.clone()));
+        assert_eq!(find_in(snapshots, "missing"), None);
+
+        restore_files(&first.path, &project).unwrap();
+        assert_eq!(
+            fs::read_to_string(project.join("pyproject.toml")).unwrap(),
+            "v1"
+        );
+        assert!(!project.join("uv.lock").exists());
+    }
+
+    #[test]
+    fn test_list_orders_same_second_snapshots_numerically() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = dir.path();
+        let meta = json!({ "label": null, "created": "2025-11-19T14:25:30Z" });
+        for id in ["20251119-142530-10", "20251119-142530", "20251119-142530-2"] {
+            fs::create_dir_all(store.join(id)).unwrap();
+            fs::write(store.join(id).join(SNAPSHOT_META_FILE), meta.to_string()).unwrap();
+        }
+
+        let ids: Vec<String> = list_in(store)
+            .unwrap()
+            .into_iter()
+            .map(|snapshot| snapshot.id)
+            .collect();
+        assert_eq!(
+            ids,
+            ["20251119-142530", "20251119-142530-2", "20251119-142530-10"]
+        );
+    }
+}
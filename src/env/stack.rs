@@ -0,0 +1,40 @@
+use crate::error::{Result, UvupError};
+use std::env;
+use std::path::PathBuf;
+
+/// Environment variable holding the venvs `uvup deactivate` returns to, oldest first
+pub(crate) const ENV_STACK_VAR: &str = "UVUP_ENV_STACK";
+
+/// Read the activation stack from the environment
+pub(crate) fn read_stack() -> Vec<PathBuf> {
+    env::var_os(ENV_STACK_VAR)
+        .map(|value| {
+            env::split_paths(&value)
+                .filter(|path| !path.as_os_str().is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Join the activation stack into the value stored in the environment
+pub(crate) fn stack_value(stack: &[PathBuf]) -> Result<String> {
+    env::join_paths(stack)
+        .map(|value| value.to_string_lossy().into_owned())
+        .map_err(|e| UvupError::PathError(format!("Cannot store activation stack: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stack_value_round_trip() {
+        let stack = vec![
+            PathBuf::from("/home/me/.uvup/base/.venv"),
+            PathBuf::from("/work/project/.venv"),
+        ];
+        let value = stack_value(&stack).unwrap();
+        let parsed: Vec<PathBuf> = env::split_paths(&value).collect();
+        assert_eq!(parsed, stack);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::env::journal::{self, FileHashes};
+use crate::env::paths::{get_env_meta_dir, get_env_path};
+use crate::env::snapshots;
+use crate::error::Result;
+use serde_json::{Value, json};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const UNDO_DIR: &str = "undo";
+const PENDING_UNDO_DIR: &str = "undo.pending";
+const UNDO_META_FILE: &str = "undo.json";
+
+/// The project files from before the last package operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct UndoState {
+    /// Command line of the operation that can be undone
+    pub(crate) command: String,
+    /// Hashes of the project files right after the operation
+    pub(crate) after: FileHashes,
+    /// Directory holding the files from before the operation
+    pub(crate) dir: PathBuf,
+}
+
+/// Run a package operation, keeping the project files from before it for `uvup undo`
+///
+/// The previous undo state is only replaced when the operation succeeds.
+/// Failing to store the new state only prints a warning.
+pub(crate) fn record<T>(name: &str, operation: impl FnOnce() -> Result<T>) -> Result<T> {
+    record_in(&get_env_path(name)?, &get_env_meta_dir(name)?, operation)
+}
+
+/// Load the undo state of an environment, if there is one
+pub(crate) fn load(name: &str) -> Result<Option<UndoState>> {
+    Ok(load_in(&get_env_meta_dir(name)?))
+}
+
+/// Forget the undo state of an environment
+pub(crate) fn clear(name: &str) -> Result<()> {
+    let dir = get_env_meta_dir(name)?.join(UNDO_DIR);
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+impl UndoState {
+    /// Whether the project files are still the ones the operation left behind
+    pub(crate) fn is_current(&self, env_path: &Path) -> bool {
+        FileHashes::read(env_path) == self.after
+    }
+}
+
+fn record_in<T>(
+    env_path: &Path,
+    meta_dir: &Path,
+    operation: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    let pending = meta_dir.join(PENDING_UNDO_DIR);
+
+    if pending.exists() {
+        fs::remove_dir_all(&pending)?;
+    }
+    snapshots::save_files(env_path, &pending)?;
+
+    let result = operation();
+
+    if result.is_ok() {
+        if let Err(e) = commit(env_path, meta_dir, &pending) {
+            let _ = writeln!(io::stderr(), "Warning: Failed to save undo state: {e}");
+        }
+    } else {
+        let _ = fs::remove_dir_all(&pending);
+    }
+
+    result
+}
+
+fn load_in(meta_dir: &Path) -> Option<UndoState> {
+    let dir = meta_dir.join(UNDO_DIR);
+    let content = fs::read_to_string(dir.join(UNDO_META_FILE)).ok()?;
+    let meta = serde_json::from_str::<Value>(&content).ok()?;
+
+    let text = |key: &str| meta[key].as_str().map(str::to_string);
+    Some(UndoState {
+        command: text("command").unwrap_or_default(),
+        after: FileHashes {
+            pyproject: text("pyproject_after"),
+            lock: text("lock_after"),
+        },
+        dir,
+    })
+}
+
+/// Turn the pending files into the undo state, replacing the previous one
+fn commit(env_path: &Path, meta_dir: &Path, pending: &Path) -> Result<()> {
+    let after = FileHashes::read(env_path);
+    let meta = json!({
+        "command": journal::command_line(),
+        "pyproject_after": after.pyproject,
+        "lock_after": after.lock,
+    });
+    fs::write(pending.join(UNDO_META_FILE), format!("{meta}\n"))?;
+
+    let undo_dir = meta_dir.join(UNDO_DIR);
+    if undo_dir.exists() {
+        fs::remove_dir_all(&undo_dir)?;
+    }
+    fs::rename(pending, undo_dir)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::UvupError;
+
+    fn project() -> (tempfile::TempDir, PathBuf, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let env_path = dir.path().join("env");
+        let meta_dir = env_path.join(".uvup");
+        fs::create_dir_all(&meta_dir).unwrap();
+        fs::write(env_path.join("pyproject.toml"), "dependencies = []\n").unwrap();
+        (dir, env_path, meta_dir)
+    }
+
+    #[test]
+    fn test_record_keeps_files_from_before() {
+        let (_dir, env_path, meta_dir) = project();
+
+        record_in(&env_path, &meta_dir, || {
+            fs::write(
+                env_path.join("pyproject.toml"),
+                "dependencies = [\"idna\"]\n",
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        let state = load_in(&meta_dir).unwrap();
+        assert!(state.is_current(&env_path));
+        assert_eq!(
+            fs::read_to_string(state.dir.join("pyproject.toml")).unwrap(),
+            "dependencies = []\n"
+        );
+        assert!(!meta_dir.join(PENDING_UNDO_DIR).exists());
+    }
+
+    #[test]
+    fn test_failed_operation_keeps_previous_state() {
+        let (_dir, env_path, meta_dir) = project();
+
+        let result: Result<()> = record_in(&env_path, &meta_dir, || {
+            Err(UvupError::CommandExecutionFailed(
+                "uv add failed".to_string(),
+            ))
+        });
+        assert!(result.is_err());
+        assert!(load_in(&meta_dir).is_none());
+
+        record_in(&env_path, &meta_dir, || Ok(())).unwrap();
+        let before = load_in(&meta_dir).unwrap();
+        let _ = record_in(&env_path, &meta_dir, || -> Result<()> {
+            fs::write(env_path.join("pyproject.toml"), "broken")?;
+            Err(UvupError::CommandExecutionFailed(
+                "uv add failed".to_string(),
+            ))
+        });
+        assert_eq!(load_in(&meta_dir), Some(before));
+        assert!(!meta_dir.join(PENDING_UNDO_DIR).exists());
+    }
+
+    #[test]
+    fn test_outside_change_is_not_current() {
+        let (_dir, env_path, meta_dir) = project();
+
+        record_in(&env_path, &meta_dir, || Ok(())).unwrap();
+        fs::write(env_path.join("uv.lock"), "version = 1\n").unwrap();
+
+        let state = load_in(&meta_dir).unwrap();
+        assert!(!state.is_current(&env_path));
+    }
+}
@@ -0,0 +1,192 @@
+use crate::env::paths::get_env_meta_dir;
+use crate::error::{Result, UvupError};
+use crate::shell::script::Action;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+
+/// Environment variable listing the variables exported by the active environment
+pub(crate) const ENV_VARS_VAR: &str = "UVUP_ENV_VARS";
+
+/// Prefix of the variables holding the values replaced on activation
+const BACKUP_PREFIX: &str = "_UVUP_BACKUP_";
+
+const ENV_VARS_FILE: &str = "env-vars.json";
+
+/// Variables managed by uvup or the venv itself
+const RESERVED_KEYS: [&str; 3] = ["PATH", "VIRTUAL_ENV", "VIRTUAL_ENV_PROMPT"];
+
+/// Load the variables configured for an environment
+pub(crate) fn load(name: &str) -> Result<BTreeMap<String, String>> {
+    let path = get_env_meta_dir(name)?.join(ENV_VARS_FILE);
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let content = fs::read_to_string(&path)?;
+    let value: Value = serde_json::from_str(&content)
+        .map_err(|e| UvupError::PathError(format!("Failed to parse {}: {e}", path.display())))?;
+
+    Ok(value
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+        .collect())
+}
+
+/// Save the variables configured for an environment
+pub(crate) fn save(name: &str, vars: &BTreeMap<String, String>) -> Result<()> {
+    let meta_dir = get_env_meta_dir(name)?;
+    fs::create_dir_all(&meta_dir)?;
+
+    let object: Map<String, Value> = vars
+        .iter()
+        .map(|(key, value)| (key.clone(), Value::from(value.as_str())))
+        .collect();
+    let mut content = serde_json::to_string_pretty(&object)
+        .map_err(|e| UvupError::PathError(format!("Failed to serialize variables: {e}")))?;
+    content.push('\n');
+
+    fs::write(meta_dir.join(ENV_VARS_FILE), content)?;
+    Ok(())
+}
+
+/// Check that a variable name can be exported by every supported shell
+pub(crate) fn validate_key(key: &str) -> Result<()> {
+    let mut chars = key.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let reserved =
+        RESERVED_KEYS.contains(&key) || key.starts_with("UVUP_") || key.starts_with(BACKUP_PREFIX);
+
+    if !valid || reserved {
+        return Err(UvupError::InvalidEnvVar(key.to_string()));
+    }
+    Ok(())
+}
+
+/// Parse a `KEY=VALUE` assignment
+pub(crate) fn parse_assignment(assignment: &str) -> Result<(String, String)> {
+    let (key, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| UvupError::InvalidEnvVar(assignment.to_string()))?;
+    validate_key(key)?;
+    Ok((key.to_string(), value.to_string()))
+}
+
+fn backup_key(key: &str) -> String {
+    format!("{BACKUP_PREFIX}{key}")
+}
+
+/// Variables exported by the currently active environment
+fn active_keys() -> Vec<String> {
+    env::var(ENV_VARS_VAR)
+        .map(|keys| {
+            keys.split(',')
+                .filter(|key| !key.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Actions restoring the values the active environment's variables replaced
+pub(crate) fn restore_actions() -> Vec<Action> {
+    let mut actions = Vec::new();
+
+    for key in active_keys() {
+        let backup = backup_key(&key);
+        match env::var(&backup) {
+            Ok(previous) => {
+                actions.push(Action::SetVar(key, previous));
+                actions.push(Action::UnsetVar(backup));
+            }
+            Err(_) => actions.push(Action::UnsetVar(key)),
+        }
+    }
+
+    if env::var_os(ENV_VARS_VAR).is_some() {
+        actions.push(Action::UnsetVar(ENV_VARS_VAR.to_string()));
+    }
+
+    actions
+}
+
+/// Actions exporting an environment's variables, backing up the values they replace
+///
+/// Meant to follow [`restore_actions`], so values set by the previously active
+/// environment are not mistaken for the user's own.
+pub(crate) fn apply_actions(vars: &BTreeMap<String, String>) -> Vec<Action> {
+    if vars.is_empty() {
+        return Vec::new();
+    }
+
+    let active = active_keys();
+    let mut actions = Vec::new();
+
+    for (key, value) in vars {
+        let previous = if active.contains(key) {
+            env::var(backup_key(key)).ok()
+        } else {
+            env::var(key).ok()
+        };
+        if let Some(previous) = previous {
+            actions.push(Action::SetVar(backup_key(key), previous));
+        }
+        actions.push(Action::SetVar(key.clone(), value.clone()));
+    }
+
+    let keys: Vec<&str> = vars.keys().map(String::as_str).collect();
+    actions.push(Action::SetVar(ENV_VARS_VAR.to_string(), keys.join(",")));
+
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_assignment() {
+        assert_eq!(
+            parse_assignment("DJANGO_SETTINGS_MODULE=app.settings").unwrap(),
+            (
+                "DJANGO_SETTINGS_MODULE".to_string(),
+                "app.settings".to_string()
+            )
+        );
+        assert_eq!(
+            parse_assignment("URL=http://x?a=b").unwrap().1,
+            "http://x?a=b"
+        );
+        assert_eq!(parse_assignment("EMPTY=").unwrap().1, "");
+
+        assert!(parse_assignment("NO_VALUE").is_err());
+        assert!(parse_assignment("1ABC=x").is_err());
+        assert!(parse_assignment("MY-VAR=x").is_err());
+        assert!(parse_assignment("PATH=/tmp").is_err());
+        assert!(parse_assignment("UVUP_ACTIVE_ENV=x").is_err());
+    }
+
+    #[test]
+    fn test_apply_actions_records_keys() {
+        let vars = BTreeMap::from([
+            ("CUDA_VISIBLE_DEVICES".to_string(), "1".to_string()),
+            ("DJANGO_SETTINGS_MODULE".to_string(), "2".to_string()),
+        ]);
+        let actions = apply_actions(&vars);
+
+        assert_eq!(
+            actions.last(),
+            Some(&Action::SetVar(
+                ENV_VARS_VAR.to_string(),
+                "CUDA_VISIBLE_DEVICES,DJANGO_SETTINGS_MODULE".to_string()
+            ))
+        );
+        assert!(apply_actions(&BTreeMap::new()).is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::env::lockfile::normalize_name;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A distribution installed in a venv
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Distribution {
+    /// Normalized package name
+    pub(crate) name: String,
+    pub(crate) version: String,
+    /// The distribution's `.dist-info` directory
+    pub(crate) dist_info: PathBuf,
+}
+
+/// Read the Python version recorded in a venv's `pyvenv.cfg`
+pub(crate) fn python_version(venv_path: &Path) -> Option<String> {
+    let config = fs::read_to_string(venv_path.join("pyvenv.cfg")).ok()?;
+
+    // uv writes `version_info`, the stdlib venv module writes `version`
+    ["version_info", "version"].iter().find_map(|wanted| {
+        config.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == *wanted).then(|| value.trim().to_string())
+        })
+    })
+}
+
+/// Get the `site-packages` directories of a venv
+fn site_packages_dirs(venv_path: &Path) -> Vec<PathBuf> {
+    if cfg!(target_os = "windows") {
+        return vec![venv_path.join("Lib").join("site-packages")];
+    }
+
+    fs::read_dir(venv_path.join("lib"))
+        .into_iter()
+        .flatten()
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("python"))
+        .map(|entry| entry.path().join("site-packages"))
+        .collect()
+}
+
+/// Whether a venv has a `site-packages` directory, i.e. has been synced
+pub(crate) fn has_site_packages(venv_path: &Path) -> bool {
+    site_packages_dirs(venv_path).iter().any(|dir| dir.is_dir())
+}
+
+/// List the distributions installed in a venv, sorted by name
+pub(crate) fn installed_distributions(venv_path: &Path) -> Vec<Distribution> {
+    let mut distributions: Vec<Distribution> = site_packages_dirs(venv_path)
+        .into_iter()
+        .flat_map(|dir| fs::read_dir(dir).into_iter().flatten())
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let stem = path.file_name()?.to_str()?.strip_suffix(".dist-info")?;
+            // Wheel installers escape `-` in names, so the first one ends the name
+            let (name, version) = stem.split_once('-')?;
+            Some(Distribution {
+                name: normalize_name(name),
+                version: version.to_string(),
+                dist_info: path,
+            })
+        })
+        .collect();
+
+    distributions.sort_by(|a, b| a.name.cmp(&b.name));
+    distributions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_python_version() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("pyvenv.cfg"),
+            "home = /usr/bin\nimplementation = CPython\nversion_info = 3.12.7\nprompt = demo\n",
+        )
+        .unwrap();
+        assert_eq!(python_version(dir.path()).as_deref(), Some("3.12.7"));
+
+        let missing = tempfile::tempdir().unwrap();
+        assert_eq!(python_version(missing.path()), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_installed_distributions() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!has_site_packages(dir.path()));
+        let site_packages = dir.path().join("lib/python3.12/site-packages");
+        for entry in [
+            "Typing_Extensions-4.12.2.dist-info",
+            "requests-2.32.3.dist-info",
+            "requests",
+        ] {
+            fs::create_dir_all(site_packages.join(entry)).unwrap();
+        }
+
+        let distributions = installed_distributions(dir.path());
+        let found: Vec<(&str, &str)> = distributions
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [("requests", "2.32.3"), ("typing-extensions", "4.12.2")]
+        );
+        assert!(has_site_packages(dir.path()));
+    }
+}
@@ -7,15 +7,33 @@ pub(crate) enum UvupError {
     EnvAlreadyExists(String),
     EnvNotFound(String),
     InvalidEnvName(String),
+    InvalidHookName(String),
     ShellDetectionFailed,
     IoError(io::Error),
     PathError(String),
     CommandExecutionFailed(String),
     UpdateFailed(String),
     NoActiveEnvironment,
+    ShellIntegrationRequired(String),
+    CompletionsNotSupported(String),
+    NotInitialized,
+    InvalidEnvVar(String),
+    SnapshotNotFound(String, String),
+    NothingToUndo(String),
+    UndoConflict(String, String),
+    LockfileMissing(String),
+    SitePackagesMissing(String),
+    InvalidVersionSpec(String),
+    PackageNotLocked(String, String),
+    IndexRequestFailed(String),
+    AdvisoryDatabaseEmpty(String),
+    VulnerabilitiesFound(usize),
+    LicenseDenied(String, usize),
 }
 
 impl fmt::Display for UvupError {
+    // One arm per variant, so this grows with the enum
+    #[allow(clippy::too_many_lines)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             UvupError::UvNotFound => {
@@ -39,9 +57,19 @@ impl fmt::Display for UvupError {
                     "Environment names must contain only alphanumeric characters, hyphens, and underscores"
                 )
             }
+            UvupError::InvalidHookName(name) => {
+                writeln!(f, "Error: Invalid hook script name '{name}'")?;
+                write!(
+                    f,
+                    "Tip: Use a plain file name without path separators or '..'"
+                )
+            }
             UvupError::ShellDetectionFailed => {
                 writeln!(f, "Error: Could not detect your shell")?;
-                write!(f, "Supported shells: bash, zsh, fish, powershell")
+                write!(
+                    f,
+                    "Supported shells: bash, zsh, fish, powershell, nushell, tcsh, xonsh"
+                )
             }
             UvupError::IoError(err) => {
                 write!(f, "IO Error: {err}")
@@ -59,9 +87,120 @@ impl fmt::Display for UvupError {
                 writeln!(f, "Error: No active environment")?;
                 write!(
                     f,
-                    "Tip: Use 'uvup activate <name>' to activate an environment first"
+                    "Tip: Use 'uvup activate <name>' to activate an environment first, or pass --env <name>"
                 )
             }
+            UvupError::ShellIntegrationRequired(command) => {
+                writeln!(
+                    f,
+                    "Error: 'uvup {command}' requires shell integration, which is not loaded"
+                )?;
+                write!(
+                    f,
+                    "Tip: Run 'uvup init' and restart your shell, or use 'eval \"$(uvup activate-script <name>)\"'"
+                )
+            }
+            UvupError::CompletionsNotSupported(shell) => {
+                writeln!(f, "Error: Completions are not available for '{shell}'")?;
+                write!(f, "Supported shells: bash, zsh, fish, powershell")
+            }
+            UvupError::NotInitialized => {
+                writeln!(f, "Error: uvup is not initialized in any shell")?;
+                write!(f, "Tip: Run 'uvup init' to set up shell integration")
+            }
+            UvupError::InvalidEnvVar(var) => {
+                writeln!(f, "Error: Invalid environment variable '{var}'")?;
+                write!(
+                    f,
+                    "Tip: Use KEY=VALUE with a name made of letters, digits and underscores; PATH, VIRTUAL_ENV and UVUP_* are managed by uvup"
+                )
+            }
+            UvupError::SnapshotNotFound(name, snapshot) => {
+                writeln!(f, "Error: Snapshot '{snapshot}' not found in '{name}'")?;
+                write!(
+                    f,
+                    "Tip: Use 'uvup snapshot list {name}' to see available snapshots"
+                )
+            }
+            UvupError::NothingToUndo(name) => {
+                writeln!(f, "Error: Nothing to undo in '{name}'")?;
+                write!(
+                    f,
+                    "Tip: Only the last 'uvup add' or 'uvup remove' can be undone"
+                )
+            }
+            UvupError::UndoConflict(name, command) => {
+                writeln!(
+                    f,
+                    "Error: pyproject.toml or uv.lock of '{name}' changed since '{command}'"
+                )?;
+                write!(
+                    f,
+                    "Tip: Use 'uvup history {name}' to see what changed, or 'uvup rollback' to restore a snapshot"
+                )
+            }
+            UvupError::LockfileMissing(name) => {
+                writeln!(f, "Error: Environment '{name}' has no uv.lock")?;
+                write!(
+                    f,
+                    "Tip: Run 'uvup lock' with the environment active to create it"
+                )
+            }
+            UvupError::SitePackagesMissing(name) => {
+                writeln!(
+                    f,
+                    "Error: Environment '{name}' has no site-packages directory in its .venv"
+                )?;
+                write!(
+                    f,
+                    "Tip: Run 'uv sync' in the environment's directory to install its packages"
+                )
+            }
+            UvupError::InvalidVersionSpec(spec) => {
+                writeln!(f, "Error: Invalid version specifier '{spec}'")?;
+                write!(
+                    f,
+                    "Tip: Use PEP 440 specifiers such as '>=2,<3', '~=1.4' or '==2.*'"
+                )
+            }
+            UvupError::PackageNotLocked(package, name) => {
+                writeln!(
+                    f,
+                    "Error: Package '{package}' is not in the uv.lock of '{name}'"
+                )?;
+                write!(
+                    f,
+                    "Tip: Use 'uvup packages {name}' to see the resolved packages"
+                )
+            }
+            UvupError::IndexRequestFailed(msg) => {
+                writeln!(f, "Error: Package index request failed: {msg}")?;
+                write!(
+                    f,
+                    "Tip: Check your network connection, or pass --index-url to use another index"
+                )
+            }
+            UvupError::AdvisoryDatabaseEmpty(dir) => {
+                writeln!(f, "Error: No PyPI advisories found in '{dir}'")?;
+                write!(
+                    f,
+                    "Tip: Point --db at an extracted OSV export, such as PyPI/all.zip from osv.dev"
+                )
+            }
+            UvupError::VulnerabilitiesFound(count) => {
+                writeln!(f, "Error: Audit failed with {count} finding(s) not ignored")?;
+                write!(
+                    f,
+                    "Tip: Upgrade the affected packages, or pass --ignore <ID> to accept a finding"
+                )
+            }
+            UvupError::LicenseDenied(name, count) => {
+                writeln!(
+                    f,
+                    "Error: {count} package(s) in '{name}' have a denied license"
+                )?;
+                write!(f, "Tip: Replace or remove them, or review the --deny rules")
+            }
         }
     }
 }
@@ -96,6 +235,9 @@ mod tests {
         let err = UvupError::InvalidEnvName("bad-name".to_string());
         assert!(err.to_string().contains("Invalid environment name"));
 
+        let err = UvupError::InvalidHookName("../x".to_string());
+        assert!(err.to_string().contains("Invalid hook script name '../x'"));
+
         let err = UvupError::ShellDetectionFailed;
         assert!(err.to_string().contains("Could not detect your shell"));
 
@@ -107,6 +249,67 @@ mod tests {
 
         let err = UvupError::UpdateFailed("network error".to_string());
         assert!(err.to_string().contains("Update failed"));
+
+        let err = UvupError::ShellIntegrationRequired("activate".to_string());
+        assert!(err.to_string().contains("uvup init"));
+
+        let err = UvupError::CompletionsNotSupported("tcsh".to_string());
+        assert!(err.to_string().contains("tcsh"));
+
+        let err = UvupError::NotInitialized;
+        assert!(err.to_string().contains("not initialized"));
+
+        let err = UvupError::InvalidEnvVar("PATH=/tmp".to_string());
+        assert!(err.to_string().contains("PATH=/tmp"));
+
+        let err = UvupError::SnapshotNotFound("myenv".to_string(), "before-upgrade".to_string());
+        assert!(err.to_string().contains("before-upgrade"));
+        assert!(err.to_string().contains("uvup snapshot list myenv"));
+
+        let err = UvupError::NothingToUndo("myenv".to_string());
+        assert!(err.to_string().contains("Nothing to undo"));
+
+        let err = UvupError::UndoConflict("myenv".to_string(), "uvup add requests".to_string());
+        assert!(
+            err.to_string()
+                .contains("changed since 'uvup add requests'")
+        );
+
+        let err = UvupError::LockfileMissing("myenv".to_string());
+        assert!(err.to_string().contains("has no uv.lock"));
+
+        let err = UvupError::SitePackagesMissing("myenv".to_string());
+        assert!(
+            err.to_string()
+                .contains("'myenv' has no site-packages directory")
+        );
+
+        let err = UvupError::InvalidVersionSpec(">>1".to_string());
+        assert!(err.to_string().contains("Invalid version specifier '>>1'"));
+
+        let err = UvupError::PackageNotLocked("rich".to_string(), "myenv".to_string());
+        assert!(err.to_string().contains("'rich' is not in the uv.lock"));
+        assert!(err.to_string().contains("uvup packages myenv"));
+
+        let err = UvupError::IndexRequestFailed("https://pypi.org/simple/x/: timeout".to_string());
+        assert!(err.to_string().contains("Package index request failed"));
+        assert!(err.to_string().contains("--index-url"));
+
+        let err = UvupError::AdvisoryDatabaseEmpty("/tmp/osv".to_string());
+        assert!(
+            err.to_string()
+                .contains("No PyPI advisories found in '/tmp/osv'")
+        );
+
+        let err = UvupError::VulnerabilitiesFound(2);
+        assert!(err.to_string().contains("2 finding(s) not ignored"));
+        assert!(err.to_string().contains("--ignore <ID>"));
+
+        let err = UvupError::LicenseDenied("product".to_string(), 1);
+        assert!(
+            err.to_string()
+                .contains("1 package(s) in 'product' have a denied license")
+        );
     }
 
     #[test]
@@ -5,18 +5,26 @@ mod error;
 mod shell;
 mod utils;
 
-use clap::Parser;
-use cli::{Cli, Commands};
-use error::Result;
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
+use cli::{Cli, Commands, EnvVarsCommand, HooksCommand, SnapshotCommand};
+use error::{Result, UvupError};
 use std::io::{self, Write};
+use std::path::Path;
 
 fn main() {
+    CompleteEnv::with_factory(Cli::command)
+        .var(commands::completions::COMPLETE_VAR)
+        .complete();
+
     if let Err(e) = run() {
         let _ = writeln!(io::stderr(), "{e}");
         std::process::exit(1);
     }
 }
 
+// A flat dispatch over all commands; splitting it would only scatter the mapping
+#[allow(clippy::too_many_lines)]
 fn run() -> Result<()> {
     let cli = Cli::parse();
 
@@ -26,7 +34,49 @@ fn run() -> Result<()> {
             raw,
             reverse,
             dry_run,
-        } => commands::init::run(shell, raw, reverse, dry_run)?,
+            completions,
+            status,
+            refresh_hooks,
+        } => commands::init::run(
+            shell.as_deref(),
+            raw,
+            reverse,
+            dry_run,
+            completions,
+            status,
+            refresh_hooks,
+        )?,
+        Commands::Completions { shell } => commands::completions::run(&shell)?,
+        Commands::Activate { .. } => {
+            return Err(UvupError::ShellIntegrationRequired("activate".to_string()));
+        }
+        Commands::Deactivate => {
+            return Err(UvupError::ShellIntegrationRequired(
+                "deactivate".to_string(),
+            ));
+        }
+        Commands::ActivateScript { name, shell, stack } => {
+            commands::activate_script::run(name, shell.as_deref(), stack)?;
+        }
+        Commands::DeactivateScript { shell } => {
+            commands::deactivate_script::run(shell.as_deref())?;
+        }
+        Commands::EnvVars { command } => match command {
+            EnvVarsCommand::Set { name, vars } => commands::env_vars::set(&name, &vars)?,
+            EnvVarsCommand::Unset { name, keys } => commands::env_vars::unset(&name, &keys)?,
+            EnvVarsCommand::List { name } => commands::env_vars::list(&name)?,
+        },
+        Commands::Hooks { command } => match command {
+            HooksCommand::List { name } => commands::hooks::list(&name)?,
+            HooksCommand::Edit {
+                name,
+                kind,
+                shell,
+                script_name,
+            } => commands::hooks::edit(&name, kind, shell.as_deref(), &script_name)?,
+        },
+        Commands::Run { name, command } => commands::run::run(name, &command)?,
+        Commands::Prompt { format } => commands::prompt::run(format.as_deref())?,
         Commands::Create { name, python } => {
             commands::create::run(name, python.as_deref())?;
         }
@@ -64,10 +114,70 @@ fn run() -> Result<()> {
             dry_run,
         )?,
         Commands::Update { check } => commands::update::run(check)?,
-        Commands::Add { packages, group } => commands::add::run(&packages, group)?,
-        Commands::Remove { packages, group } => commands::remove::run(&packages, group)?,
-        Commands::Lock { upgrade } => commands::lock::run(upgrade)?,
-        Commands::Tree { depth } => commands::tree::run(depth)?,
+        Commands::Add {
+            packages,
+            optional,
+            group,
+            dev,
+            env,
+        } => commands::add::run(env.env, &packages, optional, group, dev)?,
+        Commands::Remove {
+            packages,
+            optional,
+            group,
+            dev,
+            env,
+        } => commands::remove::run(env.env, &packages, optional, group, dev)?,
+        Commands::Lock { upgrade, env } => commands::lock::run(env.env, upgrade)?,
+        Commands::Tree { depth, env } => commands::tree::run(env.env, depth)?,
+        Commands::Packages { name, json, env } => {
+            commands::packages::run(name.or(env.env), json)?;
+        }
+        Commands::Licenses {
+            name,
+            deny,
+            json,
+            env,
+        } => commands::licenses::run(name.or(env.env), &deny, json)?,
+        Commands::Sbom { name, format, env } => {
+            commands::sbom::run(name.or(env.env), format)?;
+        }
+        Commands::SearchInstalled {
+            package,
+            version,
+            installed,
+            json,
+        } => commands::search_installed::run(&package, version.as_deref(), installed, json)?,
+        Commands::Outdated {
+            all,
+            index_url,
+            json,
+            env,
+        } => commands::outdated::run(env.env, all, index_url.as_deref(), json)?,
+        Commands::Audit {
+            all,
+            db,
+            ignore,
+            json,
+            env,
+        } => commands::audit::run(env.env, all, Path::new(&db), &ignore, json)?,
+        Commands::Why { package, json, env } => {
+            commands::why::run(&package, env.env, json)?;
+        }
+        Commands::Snapshot {
+            command,
+            name,
+            label,
+        } => match command {
+            Some(SnapshotCommand::List { name }) => commands::snapshot::list(&name)?,
+            None => commands::snapshot::create(&name.unwrap_or_default(), label.as_deref())?,
+        },
+        Commands::Rollback { name, snapshot } => commands::rollback::run(&name, &snapshot)?,
+        Commands::Diff { env_a, env_b, json } => commands::diff::run(&env_a, &env_b, json)?,
+        Commands::Undo { env } => commands::undo::run(env.env)?,
+        Commands::History { name, json, env } => {
+            commands::history::run(name.or(env.env), json)?;
+        }
     }
 
     Ok(())
@@ -1,36 +1,15 @@
+use crate::env::paths::get_venv_bin_dir;
+use crate::shell::script::Action;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
 pub(crate) const BASH_HOOK: &str = r#"
 uvup() {
     if [ "$1" = "activate" ]; then
-        if [ -z "$2" ]; then
-            echo "Error: Please specify environment name"
-            echo "Usage: uvup activate <name>"
-            return 1
-        fi
-
-        local env_path="$HOME/.uvup/$2/.venv"
-        local activate_script=""
-
-        # Check for Windows (Git Bash) or Unix paths
-        if [ -f "$env_path/Scripts/activate" ]; then
-            activate_script="$env_path/Scripts/activate"
-        elif [ -f "$env_path/bin/activate" ]; then
-            activate_script="$env_path/bin/activate"
-        fi
-
-        if [ -z "$activate_script" ]; then
-            echo "Error: Environment '$2' not found"
-            echo "Tip: Use 'uvup list' to see all available environments"
-            return 1
-        fi
-
-        if [ -n "$VIRTUAL_ENV" ]; then
-            if type deactivate > /dev/null 2>&1; then
-                deactivate
-            fi
-        fi
-
-        source "$activate_script"
-        export UVUP_ACTIVE_ENV="$2"
+        shift
+        local activate_code
+        activate_code="$(command uvup activate-script --shell bash "$@")" || return $?
+        eval "$activate_code"
 
     elif [ "$1" = "deactivate" ]; then
         if [ -n "$2" ]; then
@@ -39,15 +18,58 @@ uvup() {
             return 1
         fi
 
-        if type deactivate > /dev/null 2>&1; then
-            deactivate
-            unset UVUP_ACTIVE_ENV
-        else
-            echo "Error: No active environment"
-            return 1
-        fi
+        local deactivate_code
+        deactivate_code="$(command uvup deactivate-script --shell bash)" || return $?
+        eval "$deactivate_code"
     else
         command uvup "$@"
     fi
 }
 "#;
+
+/// Get the venv activation script for Bash and Zsh
+pub(crate) fn activate_script_path(venv_path: &Path) -> PathBuf {
+    get_venv_bin_dir(venv_path).join("activate")
+}
+
+/// Render actions as Bash/Zsh code
+pub(crate) fn render(actions: &[Action]) -> String {
+    let mut script = String::new();
+
+    for action in actions {
+        let _ = match action {
+            Action::DeactivateVenv => writeln!(
+                script,
+                "if [ -n \"${{VIRTUAL_ENV:-}}\" ] && type deactivate > /dev/null 2>&1; then deactivate; fi"
+            ),
+            Action::ActivateVenv(venv_path) => {
+                writeln!(script, ". {}", quote_path(&activate_script_path(venv_path)))
+            }
+            Action::SetVar(key, value) => writeln!(script, "export {key}={}", quote(value)),
+            Action::UnsetVar(key) => writeln!(script, "unset {key}"),
+            Action::SourceScript(path) => writeln!(script, ". {}", quote_path(path)),
+        };
+    }
+
+    script
+}
+
+/// Get the config line that sources the hook file
+pub(crate) fn source_line(hook_path: &Path) -> String {
+    format!("source {}", quote_path(hook_path))
+}
+
+/// Quote a path for Bash, using forward slashes so Git Bash accepts Windows paths
+fn quote_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if cfg!(target_os = "windows") {
+        quote(&path.replace('\\', "/"))
+    } else {
+        quote(&path)
+    }
+}
+
+/// Single-quote a value for Bash
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
@@ -6,6 +6,24 @@ pub(crate) enum ShellType {
     Zsh,
     Fish,
     PowerShell,
+    Nushell,
+    Tcsh,
+    Xonsh,
+}
+
+impl ShellType {
+    /// Canonical lowercase name, as accepted on the command line
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            ShellType::Bash => "bash",
+            ShellType::Zsh => "zsh",
+            ShellType::Fish => "fish",
+            ShellType::PowerShell => "powershell",
+            ShellType::Nushell => "nushell",
+            ShellType::Tcsh => "tcsh",
+            ShellType::Xonsh => "xonsh",
+        }
+    }
 }
 
 pub(crate) fn detect_shell() -> Result<ShellType> {
@@ -17,6 +35,12 @@ pub(crate) fn detect_shell() -> Result<ShellType> {
             return Ok(ShellType::Bash);
         } else if shell_path.contains("fish") {
             return Ok(ShellType::Fish);
+        } else if is_nushell_binary(&shell_path) {
+            return Ok(ShellType::Nushell);
+        } else if shell_path.contains("xonsh") {
+            return Ok(ShellType::Xonsh);
+        } else if shell_path.ends_with("csh") {
+            return Ok(ShellType::Tcsh);
         }
     }
 
@@ -28,6 +52,27 @@ pub(crate) fn detect_shell() -> Result<ShellType> {
     Err(UvupError::ShellDetectionFailed)
 }
 
+/// Check whether a shell path points to the Nushell binary (`nu` or `nu.exe`)
+fn is_nushell_binary(shell_path: &str) -> bool {
+    std::path::Path::new(shell_path)
+        .file_stem()
+        .is_some_and(|stem| stem == "nu")
+}
+
+/// Parse shell type from string name
+pub(crate) fn parse_shell_type(name: &str) -> Result<ShellType> {
+    match name.to_lowercase().as_str() {
+        "powershell" | "pwsh" | "ps" => Ok(ShellType::PowerShell),
+        "bash" | "git-bash" => Ok(ShellType::Bash),
+        "zsh" => Ok(ShellType::Zsh),
+        "fish" => Ok(ShellType::Fish),
+        "nu" | "nushell" => Ok(ShellType::Nushell),
+        "tcsh" | "csh" => Ok(ShellType::Tcsh),
+        "xonsh" => Ok(ShellType::Xonsh),
+        _ => Err(UvupError::PathError(format!("Unknown shell: {name}"))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,6 +84,24 @@ mod tests {
         assert_ne!(ShellType::Bash, ShellType::Zsh);
     }
 
+    #[test]
+    fn test_parse_shell_type() {
+        assert_eq!(parse_shell_type("bash").ok(), Some(ShellType::Bash));
+        assert_eq!(parse_shell_type("PWSH").ok(), Some(ShellType::PowerShell));
+        assert_eq!(parse_shell_type("fish").ok(), Some(ShellType::Fish));
+        assert_eq!(parse_shell_type("nu").ok(), Some(ShellType::Nushell));
+        assert_eq!(parse_shell_type("csh").ok(), Some(ShellType::Tcsh));
+        assert_eq!(parse_shell_type("xonsh").ok(), Some(ShellType::Xonsh));
+        assert!(parse_shell_type("cmd").is_err());
+    }
+
+    #[test]
+    fn test_is_nushell_binary() {
+        assert!(is_nushell_binary("/usr/bin/nu"));
+        assert!(is_nushell_binary("C:/Program Files/nu/bin/nu.exe"));
+        assert!(!is_nushell_binary("/gnu/store/bash"));
+    }
+
     #[test]
     fn test_detect_shell_current() {
         let result = detect_shell();
@@ -1,27 +1,13 @@
+use crate::env::paths::get_venv_bin_dir;
+use crate::shell::script::Action;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
 pub(crate) const FISH_HOOK: &str = r#"
 function uvup
     if test "$argv[1]" = "activate"
-        if test -z "$argv[2]"
-            echo "Error: Please specify environment name"
-            echo "Usage: uvup activate <name>"
-            return 1
-        end
-
-        set -l env_path "$HOME/.uvup/$argv[2]/.venv"
-        set -l activate_script "$env_path/bin/activate.fish"
-
-        if not test -f "$activate_script"
-            echo "Error: Environment '$argv[2]' not found"
-            echo "Tip: Use 'uvup list' to see all available environments"
-            return 1
-        end
-
-        if set -q VIRTUAL_ENV
-            deactivate
-        end
-
-        source "$activate_script"
-        set -gx UVUP_ACTIVE_ENV "$argv[2]"
+        command uvup activate-script --shell fish $argv[2..-1] | source
+        return $pipestatus[1]
 
     else if test "$argv[1]" = "deactivate"
         if test -n "$argv[2]"
@@ -30,15 +16,51 @@ function uvup
             return 1
         end
 
-        if functions -q deactivate
-            deactivate
-            set -e UVUP_ACTIVE_ENV
-        else
-            echo "Error: No active environment"
-            return 1
-        end
+        command uvup deactivate-script --shell fish | source
+        return $pipestatus[1]
     else
         command uvup $argv
     end
 end
 "#;
+
+/// Get the venv activation script for Fish
+pub(crate) fn activate_script_path(venv_path: &Path) -> PathBuf {
+    get_venv_bin_dir(venv_path).join("activate.fish")
+}
+
+/// Render actions as Fish code
+pub(crate) fn render(actions: &[Action]) -> String {
+    let mut script = String::new();
+
+    for action in actions {
+        let _ = match action {
+            Action::DeactivateVenv => writeln!(
+                script,
+                "if set -q VIRTUAL_ENV; and functions -q deactivate; deactivate; end"
+            ),
+            Action::ActivateVenv(venv_path) => writeln!(
+                script,
+                "source {}",
+                quote(&activate_script_path(venv_path).to_string_lossy())
+            ),
+            Action::SetVar(key, value) => writeln!(script, "set -gx {key} {}", quote(value)),
+            Action::UnsetVar(key) => writeln!(script, "set -e {key}"),
+            Action::SourceScript(path) => {
+                writeln!(script, "source {}", quote(&path.to_string_lossy()))
+            }
+        };
+    }
+
+    script
+}
+
+/// Get the config line that sources the hook file
+pub(crate) fn source_line(hook_path: &Path) -> String {
+    format!("source {}", quote(&hook_path.to_string_lossy()))
+}
+
+/// Single-quote a value for Fish
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
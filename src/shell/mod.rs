@@ -1,4 +1,9 @@
 pub(crate) mod bash;
 pub(crate) mod detect;
 pub(crate) mod fish;
+pub(crate) mod nushell;
 pub(crate) mod powershell;
+pub(crate) mod rc_block;
+pub(crate) mod script;
+pub(crate) mod tcsh;
+pub(crate) mod xonsh;
@@ -0,0 +1,125 @@
+use crate::env::paths::get_venv_bin_dir;
+use crate::shell::script::Action;
+use serde_json::{Map, Value, json};
+use std::env;
+use std::path::{Path, PathBuf};
+
+// Nushell cannot evaluate generated code, and `overlay use` only accepts paths
+// known at parse time, so activation mirrors what the venv's activate.nu does:
+// the binary prints the environment changes as JSON and the hook loads them.
+// This deliberately departs from running `overlay use` on activate.nu: the venv
+// path is only known after `uvup activate` resolves the environment name, and
+// loading the changes also lets deactivation and switching reuse the same path.
+pub(crate) const NUSHELL_HOOK: &str = r#"
+def --env --wrapped uvup [...args] {
+    if ($args | is-empty) {
+        ^uvup
+        return
+    }
+
+    if $args.0 == "activate" {
+        let result = (^uvup activate-script --shell nushell ...($args | skip 1) | complete)
+        if $result.exit_code != 0 {
+            print --stderr --no-newline $result.stderr
+            return
+        }
+
+        let changes = ($result.stdout | from json)
+        hide-env --ignore-errors ...$changes.hide
+        load-env $changes.load
+
+    } else if $args.0 == "deactivate" {
+        if ($args | length) > 1 {
+            print $"Error: Unknown command 'uvup deactivate ($args.1)'"
+            print "Did you mean: uvup deactivate"
+            return
+        }
+
+        let result = (^uvup deactivate-script --shell nushell | complete)
+        if $result.exit_code != 0 {
+            print --stderr --no-newline $result.stderr
+            return
+        }
+
+        let changes = ($result.stdout | from json)
+        hide-env --ignore-errors ...$changes.hide
+        load-env $changes.load
+    } else {
+        ^uvup ...$args
+    }
+}
+"#;
+
+/// Name of the search path variable in Nushell's `$env`
+///
+/// Nushell keeps the case Windows reports, `Path`, and `load-env` is case-sensitive,
+/// so setting `PATH` there would add a second variable instead of replacing it.
+const PATH_KEY: &str = if cfg!(windows) { "Path" } else { "PATH" };
+
+/// Get the venv activation script for Nushell
+pub(crate) fn activate_script_path(venv_path: &Path) -> PathBuf {
+    get_venv_bin_dir(venv_path).join("activate.nu")
+}
+
+/// Get the config line that sources the hook file
+///
+/// Nushell can only source files known at parse time, so the hook lives in a file.
+pub(crate) fn source_line(hook_path: &Path) -> String {
+    format!("source '{}'", hook_path.display())
+}
+
+/// Render actions as a JSON description of environment changes for the Nushell hook
+pub(crate) fn render(actions: &[Action]) -> String {
+    let mut path: Vec<PathBuf> = env::var_os("PATH")
+        .map(|p| env::split_paths(&p).collect())
+        .unwrap_or_default();
+    let mut hide: Vec<String> = Vec::new();
+    let mut load = Map::new();
+
+    for action in actions {
+        match action {
+            Action::DeactivateVenv => {
+                if let Some(active_venv) = env::var_os("VIRTUAL_ENV") {
+                    let active_bin = get_venv_bin_dir(Path::new(&active_venv));
+                    path.retain(|p| *p != active_bin);
+                    hide.push("VIRTUAL_ENV".to_string());
+                    hide.push("VIRTUAL_ENV_PROMPT".to_string());
+                }
+            }
+            Action::ActivateVenv(venv_path) => {
+                let prompt = venv_path
+                    .parent()
+                    .and_then(Path::file_name)
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                path.insert(0, get_venv_bin_dir(venv_path));
+                load.insert(
+                    "VIRTUAL_ENV".to_string(),
+                    Value::from(venv_path.to_string_lossy()),
+                );
+                load.insert("VIRTUAL_ENV_PROMPT".to_string(), Value::from(prompt));
+            }
+            Action::SetVar(key, value) => {
+                load.insert(key.clone(), Value::from(value.as_str()));
+            }
+            Action::UnsetVar(key) => {
+                load.remove(key);
+                hide.push(key.clone());
+            }
+            // Nushell cannot source files chosen at runtime
+            Action::SourceScript(_) => {}
+        }
+    }
+
+    hide.retain(|key| !load.contains_key(key));
+
+    let path: Vec<Value> = path
+        .iter()
+        .map(|p| Value::from(p.to_string_lossy()))
+        .collect();
+    load.insert(PATH_KEY.to_string(), Value::from(path));
+
+    let mut script = json!({ "hide": hide, "load": load }).to_string();
+    script.push('\n');
+    script
+}
@@ -1,3 +1,8 @@
+use crate::env::paths::get_venv_bin_dir;
+use crate::shell::script::Action;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
 pub(crate) const POWERSHELL_HOOK: &str = r#"
 function uvup {
     if ($args.Count -eq 0) {
@@ -6,27 +11,12 @@ function uvup {
     }
 
     if ($args[0] -eq "activate") {
-        if (-not $args[1]) {
-            Write-Host "Error: Please specify environment name" -ForegroundColor Red
-            Write-Host "Usage: uvup activate <name>"
-            return
-        }
-
-        $envPath = "$env:USERPROFILE\.uvup\$($args[1])\.venv"
-        $activateScript = "$envPath\Scripts\Activate.ps1"
-
-        if (-not (Test-Path $activateScript)) {
-            Write-Host "Error: Environment '$($args[1])' not found" -ForegroundColor Red
-            Write-Host "Tip: Use 'uvup list' to see all available environments"
+        $activateCode = & uvup.exe activate-script --shell powershell @($args | Select-Object -Skip 1)
+        if ($LASTEXITCODE -ne 0) {
             return
         }
 
-        if ($env:VIRTUAL_ENV) {
-            deactivate
-        }
-
-        & $activateScript
-        $env:UVUP_ACTIVE_ENV = $args[1]
+        Invoke-Expression ($activateCode -join "`n")
 
     } elseif ($args[0] -eq "deactivate") {
         if ($args[1]) {
@@ -35,14 +25,58 @@ function uvup {
             return
         }
 
-        if (Get-Command deactivate -ErrorAction SilentlyContinue) {
-            deactivate
-            Remove-Item Env:\UVUP_ACTIVE_ENV -ErrorAction SilentlyContinue
-        } else {
-            Write-Host "Error: No active environment" -ForegroundColor Red
+        $deactivateCode = & uvup.exe deactivate-script --shell powershell
+        if ($LASTEXITCODE -ne 0) {
+            return
         }
+
+        Invoke-Expression ($deactivateCode -join "`n")
     } else {
         & uvup.exe @args
     }
 }
 "#;
+
+/// Get the venv activation script for `PowerShell`
+pub(crate) fn activate_script_path(venv_path: &Path) -> PathBuf {
+    get_venv_bin_dir(venv_path).join("Activate.ps1")
+}
+
+/// Render actions as `PowerShell` code
+pub(crate) fn render(actions: &[Action]) -> String {
+    let mut script = String::new();
+
+    for action in actions {
+        let _ = match action {
+            Action::DeactivateVenv => writeln!(
+                script,
+                "if ($env:VIRTUAL_ENV -and (Get-Command deactivate -ErrorAction SilentlyContinue)) {{ deactivate }}"
+            ),
+            Action::ActivateVenv(venv_path) => writeln!(
+                script,
+                "& {}",
+                quote(&activate_script_path(venv_path).to_string_lossy())
+            ),
+            Action::SetVar(key, value) => writeln!(script, "$env:{key} = {}", quote(value)),
+            Action::SourceScript(path) => {
+                writeln!(script, ". {}", quote(&path.to_string_lossy()))
+            }
+            Action::UnsetVar(key) => writeln!(
+                script,
+                "Remove-Item Env:\\{key} -ErrorAction SilentlyContinue"
+            ),
+        };
+    }
+
+    script
+}
+
+/// Get the profile line that dot-sources the hook file
+pub(crate) fn source_line(hook_path: &Path) -> String {
+    format!(". {}", quote(&hook_path.to_string_lossy()))
+}
+
+/// Single-quote a value for `PowerShell`
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
@@ -0,0 +1,273 @@
+//! The uvup block in shell config files
+//!
+//! The block is delimited by explicit begin/end markers so it can be found,
+//! replaced and removed without touching anything around it:
+//!
+//! ```text
+//! # >>> uvup initialize >>>
+//! eval "$(uvup init --raw)"
+//! # <<< uvup initialize <<<
+//! ```
+
+pub(crate) const BLOCK_BEGIN: &str = "# >>> uvup initialize >>>";
+pub(crate) const BLOCK_END: &str = "# <<< uvup initialize <<<";
+
+/// Marker written by uvup versions before begin/end blocks were introduced
+const LEGACY_MARKER: &str = "# uvup initialization";
+
+/// Hook lines the legacy `init` wrote after [`LEGACY_MARKER`], one per shell
+const LEGACY_HOOKS: &[&str] = &[
+    "eval \"$(uvup init --raw)\"",
+    "uvup init --raw | source",
+    "Invoke-Expression ((uvup init --raw) -join \"`n\")",
+];
+
+/// A uvup block found in a config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Block {
+    /// Lines between the markers (or after a legacy marker)
+    pub(crate) lines: Vec<String>,
+    /// Whether this is a legacy single-marker block
+    pub(crate) legacy: bool,
+}
+
+/// Line ranges of blocks, as `(first line, line after the block)`
+fn block_ranges(lines: &[&str]) -> Vec<(usize, usize, bool)> {
+    let mut ranges = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i].trim();
+
+        if line == BLOCK_BEGIN {
+            // An unterminated block runs to the end of the file
+            let end = lines[i + 1..]
+                .iter()
+                .position(|l| l.trim() == BLOCK_END)
+                .map_or(lines.len(), |offset| i + 1 + offset + 1);
+            ranges.push((i, end, false));
+            i = end;
+        } else if line == LEGACY_MARKER {
+            // Legacy blocks were the marker and a single hook line; anything
+            // else after the marker belongs to the user
+            let end = if lines
+                .get(i + 1)
+                .is_some_and(|l| LEGACY_HOOKS.contains(&l.trim()))
+            {
+                i + 2
+            } else {
+                i + 1
+            };
+            ranges.push((i, end, true));
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+
+    ranges
+}
+
+/// Find all uvup blocks in config file content
+pub(crate) fn find_blocks(content: &str) -> Vec<Block> {
+    let lines: Vec<&str> = content.lines().collect();
+
+    block_ranges(&lines)
+        .into_iter()
+        .map(|(start, end, legacy)| {
+            let body_end = if legacy || lines[end - 1].trim() != BLOCK_END {
+                end
+            } else {
+                end - 1
+            };
+            Block {
+                lines: lines[start + 1..body_end]
+                    .iter()
+                    .map(|l| l.trim().to_string())
+                    .collect(),
+                legacy,
+            }
+        })
+        .collect()
+}
+
+/// Render a block with the given body lines
+fn render_block(body: &[&str]) -> Vec<String> {
+    let mut block = vec![BLOCK_BEGIN.to_string()];
+    block.extend(body.iter().map(|line| (*line).to_string()));
+    block.push(BLOCK_END.to_string());
+    block
+}
+
+/// Insert or refresh the uvup block
+///
+/// The first existing block (current or legacy) is replaced in place and any
+/// further blocks are dropped. Without an existing block, one is appended.
+pub(crate) fn upsert_block(content: &str, body: &[&str]) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let ranges = block_ranges(&lines);
+    let block = render_block(body);
+
+    let mut result: Vec<String> = Vec::with_capacity(lines.len() + block.len() + 1);
+
+    if ranges.is_empty() {
+        result.extend(lines.iter().map(|l| (*l).to_string()));
+        if result.last().is_some_and(|l| !l.trim().is_empty()) {
+            result.push(String::new());
+        }
+        result.extend(block);
+    } else {
+        let mut next = 0;
+        for (index, (start, end, _)) in ranges.into_iter().enumerate() {
+            result.extend(lines[next..start].iter().map(|l| (*l).to_string()));
+            if index == 0 {
+                result.extend(block.iter().cloned());
+            }
+            next = end;
+        }
+        result.extend(lines[next..].iter().map(|l| (*l).to_string()));
+    }
+
+    join_lines(&result)
+}
+
+/// Remove all uvup blocks (current and legacy), leaving other lines untouched
+pub(crate) fn remove_blocks(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let ranges = block_ranges(&lines);
+
+    if ranges.is_empty() {
+        return content.to_string();
+    }
+
+    let mut result: Vec<String> = Vec::with_capacity(lines.len());
+    let mut next = 0;
+    for (start, end, _) in ranges {
+        let kept = &lines[next..start];
+        result.extend(kept.iter().map(|l| (*l).to_string()));
+        next = end;
+
+        // Drop the blank separator line uvup added before the block
+        if result.last().is_some_and(|l| l.trim().is_empty())
+            && lines.get(next).is_none_or(|l| l.trim().is_empty())
+        {
+            result.pop();
+        }
+    }
+    result.extend(lines[next..].iter().map(|l| (*l).to_string()));
+
+    join_lines(&result)
+}
+
+fn join_lines(lines: &[String]) -> String {
+    if lines.is_empty() {
+        String::new()
+    } else {
+        let mut joined = lines.join("\n");
+        joined.push('\n');
+        joined
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOOK: &str = "eval \"$(uvup init --raw)\"";
+
+    #[test]
+    fn test_upsert_appends_block() {
+        let content = "export A=1\n";
+        let updated = upsert_block(content, &[HOOK]);
+
+        assert_eq!(
+            updated,
+            format!("export A=1\n\n{BLOCK_BEGIN}\n{HOOK}\n{BLOCK_END}\n")
+        );
+        assert_eq!(
+            upsert_block("", &[HOOK]),
+            format!("{BLOCK_BEGIN}\n{HOOK}\n{BLOCK_END}\n")
+        );
+    }
+
+    #[test]
+    fn test_upsert_replaces_in_place() {
+        let content = format!(
+            "export A=1\n{BLOCK_BEGIN}\nold line\n{BLOCK_END}\nexport B=2\n{BLOCK_BEGIN}\n{HOOK}\n{BLOCK_END}\n"
+        );
+        let updated = upsert_block(&content, &[HOOK]);
+
+        assert_eq!(
+            updated,
+            format!("export A=1\n{BLOCK_BEGIN}\n{HOOK}\n{BLOCK_END}\nexport B=2\n")
+        );
+        assert_eq!(upsert_block(&updated, &[HOOK]), updated);
+    }
+
+    #[test]
+    fn test_upsert_migrates_legacy_block() {
+        let content = format!("export A=1\n\n{LEGACY_MARKER}\n{HOOK}\nalias ll='ls -l'\n");
+        let updated = upsert_block(&content, &[HOOK]);
+
+        assert_eq!(
+            updated,
+            format!("export A=1\n\n{BLOCK_BEGIN}\n{HOOK}\n{BLOCK_END}\nalias ll='ls -l'\n")
+        );
+    }
+
+    #[test]
+    fn test_legacy_block_is_marker_and_hook_only() {
+        let content = format!("{LEGACY_MARKER}\n{HOOK}\nalias ul=\"uvup list\"\n");
+        assert_eq!(
+            upsert_block(&content, &[HOOK]),
+            format!("{BLOCK_BEGIN}\n{HOOK}\n{BLOCK_END}\nalias ul=\"uvup list\"\n")
+        );
+        assert_eq!(remove_blocks(&content), "alias ul=\"uvup list\"\n");
+
+        // A marker without a known hook line after it is dropped on its own
+        let content = format!("{LEGACY_MARKER}\nuvup list\n");
+        assert_eq!(remove_blocks(&content), "uvup list\n");
+
+        let fish = format!("{LEGACY_MARKER}\nuvup init --raw | source\nuvup list\n");
+        assert_eq!(remove_blocks(&fish), "uvup list\n");
+    }
+
+    #[test]
+    fn test_remove_keeps_surrounding_content() {
+        let content = format!(
+            "export A=1\n\n{BLOCK_BEGIN}\n{HOOK}\n{BLOCK_END}\nalias uvup-ls='uvup list'\n"
+        );
+        assert_eq!(
+            remove_blocks(&content),
+            "export A=1\n\nalias uvup-ls='uvup list'\n"
+        );
+
+        let appended = upsert_block("export A=1\n", &[HOOK]);
+        assert_eq!(remove_blocks(&appended), "export A=1\n");
+    }
+
+    #[test]
+    fn test_remove_legacy_block() {
+        let content = format!("{LEGACY_MARKER}\n{HOOK}\n\n# user comment mentioning uvup init\n");
+        assert_eq!(
+            remove_blocks(&content),
+            "\n# user comment mentioning uvup init\n"
+        );
+        assert_eq!(remove_blocks("export A=1"), "export A=1");
+    }
+
+    #[test]
+    fn test_find_blocks() {
+        let content = format!(
+            "{LEGACY_MARKER}\n{HOOK}\n\n{BLOCK_BEGIN}\n{HOOK}\nuvup completions\n{BLOCK_END}\n"
+        );
+        let blocks = find_blocks(&content);
+
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[0].legacy);
+        assert_eq!(
+            blocks[1].lines,
+            vec![HOOK.to_string(), "uvup completions".to_string()]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use crate::shell::detect::ShellType;
+use crate::shell::{bash, fish, nushell, powershell, tcsh, xonsh};
+use std::path::{Path, PathBuf};
+
+/// A single step of a generated activation script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Action {
+    /// Deactivate the currently active virtual environment, if any
+    DeactivateVenv,
+    /// Activate the virtual environment at the given path
+    ActivateVenv(PathBuf),
+    /// Export an environment variable
+    SetVar(String, String),
+    /// Remove an environment variable
+    UnsetVar(String),
+    /// Source a script in the current shell
+    SourceScript(PathBuf),
+}
+
+/// Render actions as code for the given shell to evaluate
+pub(crate) fn render(shell: ShellType, actions: &[Action]) -> String {
+    match shell {
+        ShellType::Bash | ShellType::Zsh => bash::render(actions),
+        ShellType::Fish => fish::render(actions),
+        ShellType::PowerShell => powershell::render(actions),
+        ShellType::Nushell => nushell::render(actions),
+        ShellType::Tcsh => tcsh::render(actions),
+        ShellType::Xonsh => xonsh::render(actions),
+    }
+}
+
+/// Get the venv activation script used by the given shell
+pub(crate) fn venv_activate_script(shell: ShellType, venv_path: &Path) -> PathBuf {
+    match shell {
+        ShellType::Bash | ShellType::Zsh => bash::activate_script_path(venv_path),
+        ShellType::Fish => fish::activate_script_path(venv_path),
+        ShellType::PowerShell => powershell::activate_script_path(venv_path),
+        ShellType::Nushell => nushell::activate_script_path(venv_path),
+        ShellType::Tcsh => tcsh::activate_script_path(venv_path),
+        ShellType::Xonsh => xonsh::activate_script_path(venv_path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activation() -> Vec<Action> {
+        vec![
+            Action::DeactivateVenv,
+            Action::ActivateVenv(PathBuf::from("/home/me/.uvup/it's/.venv")),
+            Action::SetVar("UVUP_ACTIVE_ENV".to_string(), "it's".to_string()),
+        ]
+    }
+
+    fn deactivation() -> Vec<Action> {
+        vec![
+            Action::DeactivateVenv,
+            Action::UnsetVar("UVUP_ACTIVE_ENV".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_render_unset_var() {
+        assert!(render(ShellType::Bash, &deactivation()).contains("unset UVUP_ACTIVE_ENV"));
+        assert!(render(ShellType::Fish, &deactivation()).contains("set -e UVUP_ACTIVE_ENV"));
+        assert!(
+            render(ShellType::PowerShell, &deactivation())
+                .contains("Remove-Item Env:\\UVUP_ACTIVE_ENV")
+        );
+        assert!(render(ShellType::Tcsh, &deactivation()).contains("unsetenv UVUP_ACTIVE_ENV"));
+        assert!(
+            render(ShellType::Xonsh, &deactivation())
+                .contains("${...}.pop(\"UVUP_ACTIVE_ENV\", None)")
+        );
+
+        let changes: serde_json::Value =
+            serde_json::from_str(&render(ShellType::Nushell, &deactivation())).unwrap();
+        assert!(
+            changes["hide"]
+                .as_array()
+                .unwrap()
+                .contains(&serde_json::Value::from("UVUP_ACTIVE_ENV"))
+        );
+    }
+
+    #[test]
+    fn test_render_source_script() {
+        let actions = [Action::SourceScript(PathBuf::from("/envs/it's/setup.sh"))];
+        assert_eq!(
+            render(ShellType::Bash, &actions),
+            ". '/envs/it'\\''s/setup.sh'\n"
+        );
+        assert_eq!(
+            render(ShellType::Tcsh, &actions),
+            "source '/envs/it'\\''s/setup.sh'\n"
+        );
+    }
+
+    #[test]
+    fn test_render_bash() {
+        let script = render(ShellType::Bash, &activation());
+        assert!(script.contains("deactivate"));
+        assert!(script.contains("export UVUP_ACTIVE_ENV='it'\\''s'"));
+    }
+
+    #[test]
+    fn test_render_fish() {
+        let script = render(ShellType::Fish, &activation());
+        assert!(script.contains("activate.fish"));
+        assert!(script.contains("set -gx UVUP_ACTIVE_ENV 'it\\'s'"));
+    }
+
+    #[test]
+    fn test_render_powershell() {
+        let script = render(ShellType::PowerShell, &activation());
+        assert!(script.contains("Activate.ps1"));
+        assert!(script.contains("$env:UVUP_ACTIVE_ENV = 'it''s'"));
+    }
+
+    #[test]
+    fn test_render_nushell() {
+        let script = render(ShellType::Nushell, &activation());
+        let changes: serde_json::Value = serde_json::from_str(&script).unwrap();
+        assert_eq!(changes["load"]["UVUP_ACTIVE_ENV"], "it's");
+        assert_eq!(changes["load"]["VIRTUAL_ENV_PROMPT"], "it's");
+        let path_key = if cfg!(windows) { "Path" } else { "PATH" };
+        assert!(changes["load"][path_key].is_array());
+    }
+
+    #[test]
+    fn test_render_tcsh() {
+        let script = render(ShellType::Tcsh, &activation());
+        assert_eq!(script.lines().count(), 1);
+        assert!(script.contains("activate.csh"));
+        assert!(script.contains("setenv UVUP_ACTIVE_ENV 'it'\\''s'"));
+    }
+
+    #[test]
+    fn test_render_xonsh() {
+        let script = render(ShellType::Xonsh, &activation());
+        assert!(script.contains("$PATH.insert(0, "));
+        assert!(script.contains("$UVUP_ACTIVE_ENV = \"it's\""));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::env::paths::get_venv_bin_dir;
+use crate::shell::script::Action;
+use std::path::{Path, PathBuf};
+
+// Sourced by the `uvup` alias with the command arguments in $argv.
+// `\uvup` bypasses the alias to reach the binary.
+pub(crate) const TCSH_HOOK: &str = r#"
+if ( $#argv == 0 ) then
+    \uvup
+else if ( "$argv[1]" == "activate" ) then
+    eval "`\uvup activate-script --shell tcsh $argv[2-]:q`"
+
+else if ( "$argv[1]" == "deactivate" ) then
+    if ( $#argv > 1 ) then
+        echo "Error: Unknown command 'uvup deactivate $argv[2]'"
+        echo "Did you mean: uvup deactivate"
+    else
+        eval "`\uvup deactivate-script --shell tcsh`"
+    endif
+else
+    \uvup $argv:q
+endif
+"#;
+
+/// Get the venv activation script for tcsh
+pub(crate) fn activate_script_path(venv_path: &Path) -> PathBuf {
+    get_venv_bin_dir(venv_path).join("activate.csh")
+}
+
+/// Get the config line that aliases `uvup` to the hook file
+///
+/// tcsh has no functions, so the alias sources the hook with the command arguments.
+pub(crate) fn source_line(hook_path: &Path) -> String {
+    format!("alias uvup 'source \"{}\" \\!*'", hook_path.display())
+}
+
+/// Render actions as tcsh code
+///
+/// Statements are joined on a single line because backquote substitution
+/// turns newlines into word separators before `eval` sees them.
+pub(crate) fn render(actions: &[Action]) -> String {
+    let statements: Vec<String> = actions
+        .iter()
+        .map(|action| match action {
+            // A single-line `if` cannot run an alias directly, so go through `eval`
+            Action::DeactivateVenv => "if ( $?VIRTUAL_ENV ) eval deactivate".to_string(),
+            Action::ActivateVenv(venv_path) => format!(
+                "source {}",
+                quote(&activate_script_path(venv_path).to_string_lossy())
+            ),
+            Action::SetVar(key, value) => format!("setenv {key} {}", quote(value)),
+            Action::UnsetVar(key) => format!("unsetenv {key}"),
+            Action::SourceScript(path) => format!("source {}", quote(&path.to_string_lossy())),
+        })
+        .collect();
+
+    format!("{}\n", statements.join("; "))
+}
+
+/// Single-quote a value for tcsh, escaping history expansion
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''").replace('!', "\\!"))
+}
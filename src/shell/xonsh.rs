@@ -0,0 +1,106 @@
+use crate::env::paths::get_venv_bin_dir;
+use crate::shell::script::Action;
+use serde_json::Value;
+use std::env;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+pub(crate) const XONSH_HOOK: &str = r#"
+import shutil as _uvup_shutil
+import subprocess as _uvup_subprocess
+
+
+def _uvup(args):
+    exe = _uvup_shutil.which("uvup")
+
+    if args and args[0] == "activate":
+        result = _uvup_subprocess.run(
+            [exe, "activate-script", "--shell", "xonsh", *args[1:]],
+            stdout=_uvup_subprocess.PIPE,
+            text=True,
+        )
+        if result.returncode != 0:
+            return result.returncode
+        execx(result.stdout)
+
+    elif args and args[0] == "deactivate":
+        if len(args) > 1:
+            print(f"Error: Unknown command 'uvup deactivate {args[1]}'")
+            print("Did you mean: uvup deactivate")
+            return 1
+
+        result = _uvup_subprocess.run(
+            [exe, "deactivate-script", "--shell", "xonsh"],
+            stdout=_uvup_subprocess.PIPE,
+            text=True,
+        )
+        if result.returncode != 0:
+            return result.returncode
+        execx(result.stdout)
+
+    else:
+        return _uvup_subprocess.run([exe, *args]).returncode
+
+
+aliases["uvup"] = _uvup
+"#;
+
+/// Get the venv activation script for Xonsh
+///
+/// Xonsh activates venvs the way vox does, by editing `$PATH` and `$VIRTUAL_ENV`
+/// directly, so this is only used to check that the venv exists.
+pub(crate) fn activate_script_path(venv_path: &Path) -> PathBuf {
+    get_venv_bin_dir(venv_path).join("activate_this.py")
+}
+
+/// Get the config line that sources the hook file
+pub(crate) fn source_line(hook_path: &Path) -> String {
+    format!("source '{}'", hook_path.display())
+}
+
+/// Render actions as Xonsh code
+pub(crate) fn render(actions: &[Action]) -> String {
+    let mut script = String::new();
+
+    for action in actions {
+        let _ = match action {
+            Action::DeactivateVenv => match env::var_os("VIRTUAL_ENV") {
+                Some(active_venv) => {
+                    let active_bin =
+                        quote(&get_venv_bin_dir(Path::new(&active_venv)).to_string_lossy());
+                    writeln!(
+                        script,
+                        "if {active_bin} in $PATH:\n    $PATH.remove({active_bin})\n${{...}}.pop('VIRTUAL_ENV', None)\n${{...}}.pop('VIRTUAL_ENV_PROMPT', None)"
+                    )
+                }
+                None => Ok(()),
+            },
+            Action::ActivateVenv(venv_path) => {
+                let prompt = venv_path
+                    .parent()
+                    .and_then(Path::file_name)
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                writeln!(
+                    script,
+                    "$PATH.insert(0, {})\n$VIRTUAL_ENV = {}\n$VIRTUAL_ENV_PROMPT = {}",
+                    quote(&get_venv_bin_dir(venv_path).to_string_lossy()),
+                    quote(&venv_path.to_string_lossy()),
+                    quote(&prompt)
+                )
+            }
+            Action::SetVar(key, value) => writeln!(script, "${key} = {}", quote(value)),
+            Action::UnsetVar(key) => writeln!(script, "${{...}}.pop({}, None)", quote(key)),
+            Action::SourceScript(path) => {
+                writeln!(script, "source {}", quote(&path.to_string_lossy()))
+            }
+        };
+    }
+
+    script
+}
+
+/// Quote a value as a Python string literal (JSON strings are valid Python)
+fn quote(value: &str) -> String {
+    Value::from(value).to_string()
+}
@@ -1,6 +1,9 @@
 use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
 use std::path::PathBuf;
 use std::process::Command;
+use std::thread;
 
 fn get_test_env_dir() -> PathBuf {
     dirs::home_dir()
@@ -15,6 +18,42 @@ fn cleanup_test_env(name: &str) {
     }
 }
 
+/// Serve PEP 691 project pages from a local stand-in index, returning its URL
+fn serve_simple_index(projects: Vec<(&'static str, serde_json::Value)>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind test index");
+    let url = format!("http://{}/simple", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut request_line = String::new();
+            BufReader::new(&stream).read_line(&mut request_line).ok();
+            let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+
+            let page = projects
+                .iter()
+                .find(|(name, _)| path == format!("/simple/{name}/"))
+                .map(|(_, page)| page.to_string());
+            let response = match page {
+                // A null page stands for a failing index
+                Some(body) if body == "null" => {
+                    "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_string()
+                }
+                Some(body) => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/vnd.pypi.simple.v1+json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                ),
+                None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string(),
+            };
+            stream.write_all(response.as_bytes()).ok();
+        }
+    });
+
+    url
+}
+
 #[test]
 fn test_init_command() {
     // Test --raw flag to get shell script output
@@ -43,6 +82,1056 @@ fn test_init_command() {
     assert!(stdout.contains("deactivate"));
 }
 
+#[test]
+fn test_init_raw_honours_shell_argument() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "init", "--raw", "fish"])
+        .output()
+        .expect("Failed to execute uvup init --raw fish");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("function uvup"));
+    assert!(stdout.contains("--shell fish"));
+}
+
+#[test]
+fn test_completions_command() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "completions", "bash"])
+        .output()
+        .expect("Failed to execute uvup completions");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("complete"));
+    assert!(stdout.contains("uvup"));
+
+    let unsupported = Command::new("cargo")
+        .args(["run", "--", "completions", "tcsh"])
+        .output()
+        .expect("Failed to execute uvup completions");
+
+    assert!(!unsupported.status.success());
+}
+
+#[test]
+fn test_activate_script_nonexistent_environment() {
+    let test_env = "nonexistent-activate-env";
+
+    cleanup_test_env(test_env);
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "activate-script", test_env, "--shell", "bash"])
+        .output()
+        .expect("Failed to execute uvup activate-script");
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not found"));
+}
+
+#[test]
+fn test_deactivate_script_without_active_environment() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "deactivate-script", "--shell", "bash"])
+        .env_remove("VIRTUAL_ENV")
+        .output()
+        .expect("Failed to execute uvup deactivate-script");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No active environment"));
+}
+
+#[test]
+fn test_deactivate_script_skips_missing_stacked_venv() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "deactivate-script", "--shell", "bash"])
+        .env("VIRTUAL_ENV", "/nonexistent/uvup-test/.venv")
+        .env("UVUP_ENV_STACK", "/nonexistent/uvup-stacked/.venv")
+        .output()
+        .expect("Failed to execute uvup deactivate-script");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("deactivate"));
+    assert!(stdout.contains("unset UVUP_ENV_STACK"));
+    assert!(!stdout.contains("uvup-stacked"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_env_vars_and_run() {
+    let test_env = "test-env-vars-run";
+
+    cleanup_test_env(test_env);
+
+    // A minimal environment layout is enough for env-vars and run
+    let bin_dir = get_test_env_dir().join(test_env).join(".venv").join("bin");
+    fs::create_dir_all(&bin_dir).expect("Failed to create test environment");
+    fs::write(bin_dir.join("activate"), "").expect("Failed to create activate script");
+
+    let set_output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "env-vars",
+            "set",
+            test_env,
+            "TEST_GREETING=hello world",
+        ])
+        .output()
+        .expect("Failed to execute uvup env-vars set");
+    assert!(set_output.status.success());
+
+    let list_output = Command::new("cargo")
+        .args(["run", "--", "env-vars", "list", test_env])
+        .output()
+        .expect("Failed to execute uvup env-vars list");
+    let stdout = String::from_utf8_lossy(&list_output.stdout);
+    assert!(stdout.contains("TEST_GREETING=hello world"));
+
+    let run_output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "run",
+            test_env,
+            "sh",
+            "-c",
+            "echo \"$UVUP_ACTIVE_ENV:$TEST_GREETING\"; exit 3",
+        ])
+        .output()
+        .expect("Failed to execute uvup run");
+    assert_eq!(run_output.status.code(), Some(3));
+    let stdout = String::from_utf8_lossy(&run_output.stdout);
+    assert!(stdout.contains("test-env-vars-run:hello world"));
+
+    cleanup_test_env(test_env);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_hooks_edit_list_and_activate() {
+    let test_env = "test-env-hooks";
+
+    cleanup_test_env(test_env);
+
+    let bin_dir = get_test_env_dir().join(test_env).join(".venv").join("bin");
+    fs::create_dir_all(&bin_dir).expect("Failed to create test environment");
+    fs::write(bin_dir.join("activate"), "").expect("Failed to create activate script");
+
+    let edit_output = Command::new("cargo")
+        .args([
+            "run", "--", "hooks", "edit", test_env, "activate", "--shell", "bash",
+        ])
+        .env("EDITOR", "true")
+        .env_remove("VISUAL")
+        .output()
+        .expect("Failed to execute uvup hooks edit");
+    assert!(edit_output.status.success());
+
+    let list_output = Command::new("cargo")
+        .args(["run", "--", "hooks", "list", test_env])
+        .output()
+        .expect("Failed to execute uvup hooks list");
+    let stdout = String::from_utf8_lossy(&list_output.stdout);
+    assert!(stdout.contains("activate.d/default.sh"));
+
+    let activate_output = Command::new("cargo")
+        .args(["run", "--", "activate-script", test_env, "--shell", "bash"])
+        .env_remove("UVUP_ACTIVE_ENV")
+        .output()
+        .expect("Failed to execute uvup activate-script");
+    let stdout = String::from_utf8_lossy(&activate_output.stdout);
+    assert!(stdout.trim_end().ends_with("activate.d/default.sh'"));
+
+    // Script names cannot escape the hook directory
+    let escape_output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "hooks",
+            "edit",
+            test_env,
+            "activate",
+            "--shell",
+            "bash",
+            "--name",
+            "../../escaped",
+        ])
+        .env("EDITOR", "true")
+        .env_remove("VISUAL")
+        .output()
+        .expect("Failed to execute uvup hooks edit");
+    assert!(!escape_output.status.success());
+    let stderr = String::from_utf8_lossy(&escape_output.stderr);
+    assert!(stderr.contains("Invalid hook script name"));
+    assert!(
+        !get_test_env_dir()
+            .join(test_env)
+            .join("escaped.sh")
+            .exists()
+    );
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_env_vars_rejects_reserved_names() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "env-vars", "set", "any-env", "PATH=/tmp"])
+        .output()
+        .expect("Failed to execute uvup env-vars set");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid environment variable 'PATH'"));
+}
+
+#[test]
+fn test_history_records_lock_upgrade() {
+    let test_env = "test-env-history";
+
+    cleanup_test_env(test_env);
+
+    let env_dir = get_test_env_dir().join(test_env);
+    fs::create_dir_all(env_dir.join(".venv")).expect("Failed to create test environment");
+    fs::write(
+        env_dir.join("pyproject.toml"),
+        "[project]\nname = \"test-env-history\"\nversion = \"0.1.0\"\nrequires-python = \">=3.8\"\ndependencies = []\n",
+    )
+    .expect("Failed to create pyproject.toml");
+
+    // Plain locking is not journaled; the outcome of the upgrade depends on uv being installed
+    for args in [&["lock"][..], &["lock", "--upgrade"]] {
+        Command::new("cargo")
+            .args(["run", "--"])
+            .args(args)
+            .env("UVUP_ACTIVE_ENV", test_env)
+            .env("USER", "history-tester")
+            .output()
+            .expect("Failed to execute uvup lock");
+    }
+
+    let history_output = Command::new("cargo")
+        .args(["run", "--", "history", test_env, "--json"])
+        .output()
+        .expect("Failed to execute uvup history");
+    assert!(history_output.status.success());
+
+    let stdout = String::from_utf8_lossy(&history_output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].contains("\"command\":\"uvup lock --upgrade\""));
+    assert!(lines[0].contains("\"pyproject_before\":\""));
+    assert!(lines[0].contains("\"user\":\"history-tester\""));
+
+    let readable_output = Command::new("cargo")
+        .args(["run", "--", "history", test_env])
+        .output()
+        .expect("Failed to execute uvup history");
+    assert!(String::from_utf8_lossy(&readable_output.stdout).contains("history-tester@"));
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_snapshot_list_and_rollback_missing_snapshot() {
+    let test_env = "test-env-snapshot";
+
+    cleanup_test_env(test_env);
+
+    let env_dir = get_test_env_dir().join(test_env);
+    fs::create_dir_all(env_dir.join(".venv")).expect("Failed to create test environment");
+    fs::write(env_dir.join("pyproject.toml"), "[project]\n")
+        .expect("Failed to create pyproject.toml");
+
+    let snapshot_output = Command::new("cargo")
+        .args(["run", "--", "snapshot", test_env, "before-upgrade"])
+        .output()
+        .expect("Failed to execute uvup snapshot");
+    assert!(snapshot_output.status.success());
+
+    let list_output = Command::new("cargo")
+        .args(["run", "--", "snapshot", "list", test_env])
+        .output()
+        .expect("Failed to execute uvup snapshot list");
+    let stdout = String::from_utf8_lossy(&list_output.stdout);
+    assert!(stdout.contains("before-upgrade"));
+
+    let rollback_output = Command::new("cargo")
+        .args(["run", "--", "rollback", test_env, "missing"])
+        .output()
+        .expect("Failed to execute uvup rollback");
+    assert!(!rollback_output.status.success());
+    let stderr = String::from_utf8_lossy(&rollback_output.stderr);
+    assert!(stderr.contains("Snapshot 'missing' not found"));
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_undo_without_previous_operation() {
+    let test_env = "test-env-undo";
+
+    cleanup_test_env(test_env);
+
+    let env_dir = get_test_env_dir().join(test_env);
+    fs::create_dir_all(env_dir.join(".venv")).expect("Failed to create test environment");
+    fs::write(env_dir.join("pyproject.toml"), "[project]\n")
+        .expect("Failed to create pyproject.toml");
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "undo"])
+        .env("UVUP_ACTIVE_ENV", test_env)
+        .output()
+        .expect("Failed to execute uvup undo");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Nothing to undo"));
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_diff_environments() {
+    let env_a = "test-env-diff-a";
+    let env_b = "test-env-diff-b";
+
+    for (name, numpy) in [(env_a, "1.26.4"), (env_b, "2.0.0")] {
+        cleanup_test_env(name);
+        let env_dir = get_test_env_dir().join(name);
+        fs::create_dir_all(env_dir.join(".venv")).expect("Failed to create test environment");
+        fs::write(
+            env_dir.join("pyproject.toml"),
+            format!("[project]\nname = \"{name}\"\ndependencies = [\"numpy\"]\n"),
+        )
+        .expect("Failed to create pyproject.toml");
+        fs::write(
+            env_dir.join("uv.lock"),
+            format!("[[package]]\nname = \"numpy\"\nversion = \"{numpy}\"\n"),
+        )
+        .expect("Failed to create uv.lock");
+    }
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "diff", env_a, env_b])
+        .output()
+        .expect("Failed to execute uvup diff");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("~ numpy 1.26.4 -> numpy 2.0.0"));
+
+    let json_output = Command::new("cargo")
+        .args(["run", "--", "diff", env_a, env_b, "--json"])
+        .output()
+        .expect("Failed to execute uvup diff --json");
+    let report: serde_json::Value =
+        serde_json::from_slice(&json_output.stdout).expect("diff --json prints JSON");
+    assert_eq!(report["packages"]["changed"]["numpy"]["b"], "2.0.0");
+    assert_eq!(report["python"], serde_json::Value::Null);
+
+    cleanup_test_env(env_a);
+    cleanup_test_env(env_b);
+}
+
+#[test]
+fn test_packages_lists_locked_packages() {
+    let test_env = "test-env-packages";
+
+    cleanup_test_env(test_env);
+
+    let env_dir = get_test_env_dir().join(test_env);
+    fs::create_dir_all(env_dir.join(".venv")).expect("Failed to create test environment");
+    fs::write(
+        env_dir.join("uv.lock"),
+        r#"
+[[package]]
+name = "certifi"
+version = "2024.8.30"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "test-env-packages"
+version = "0.1.0"
+source = { virtual = "." }
+dependencies = [{ name = "requests" }]
+
+[[package]]
+name = "requests"
+version = "2.32.3"
+source = { registry = "https://pypi.org/simple" }
+dependencies = [{ name = "certifi" }]
+"#,
+    )
+    .expect("Failed to create uv.lock");
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "packages", test_env, "--json"])
+        .output()
+        .expect("Failed to execute uvup packages");
+    assert!(output.status.success());
+
+    let packages: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("packages --json prints JSON");
+    assert_eq!(packages.as_array().map(Vec::len), Some(2));
+    assert_eq!(packages[0]["name"], "certifi");
+    assert_eq!(packages[0]["kind"], "transitive");
+    assert_eq!(packages[1]["name"], "requests");
+    assert_eq!(packages[1]["kind"], "direct");
+    assert_eq!(packages[1]["source"], "registry");
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_search_installed_filters_by_version() {
+    let envs = [
+        (
+            "test-env-search-old",
+            "version = \"1.4.2\"\nsource = { registry = \"https://pypi.org/simple\" }",
+        ),
+        (
+            "test-env-search-new",
+            "version = \"2.1.0\"\nsource = { registry = \"https://pypi.org/simple\" }",
+        ),
+        // Git and dynamic packages have no version in uv.lock
+        (
+            "test-env-search-git",
+            "source = { git = \"https://example.com/probe.git#0123abc\" }",
+        ),
+    ];
+
+    for (env, package) in envs {
+        cleanup_test_env(env);
+        let env_dir = get_test_env_dir().join(env);
+        // Environments are only listed once they have an activation script
+        let (bin_dir, script) = if cfg!(windows) {
+            ("Scripts", "Activate.ps1")
+        } else {
+            ("bin", "activate")
+        };
+        let bin_dir = env_dir.join(".venv").join(bin_dir);
+        fs::create_dir_all(&bin_dir).expect("Failed to create test environment");
+        fs::write(bin_dir.join(script), "").expect("Failed to create activation script");
+        fs::write(
+            env_dir.join("uv.lock"),
+            format!(
+                r#"
+[[package]]
+name = "{env}"
+version = "0.1.0"
+source = {{ virtual = "." }}
+dependencies = [{{ name = "uvup-search-probe" }}]
+
+[[package]]
+name = "uvup-search-probe"
+{package}
+"#
+            ),
+        )
+        .expect("Failed to create uv.lock");
+    }
+
+    let search = |extra: &[&str]| -> Vec<String> {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--",
+                "search-installed",
+                "Uvup_Search_Probe",
+                "--json",
+            ])
+            .args(extra)
+            .output()
+            .expect("Failed to execute uvup search-installed");
+        assert!(output.status.success());
+
+        let matches: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("search-installed --json prints JSON");
+        matches
+            .as_array()
+            .expect("a JSON array")
+            .iter()
+            .inspect(|m| assert_eq!(m["direct"], true))
+            .filter_map(|m| m["env"].as_str().map(str::to_string))
+            .filter(|env| env.starts_with("test-env-search-"))
+            .collect()
+    };
+
+    assert_eq!(search(&[]).len(), 3);
+    assert_eq!(search(&["--version", ">=2,<3"]), ["test-env-search-new"]);
+    assert_eq!(search(&["--version", "~=1.4"]), ["test-env-search-old"]);
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "search-installed", "probe", "--version", ">>1"])
+        .output()
+        .expect("Failed to execute uvup search-installed");
+    assert!(!output.status.success());
+
+    for (env, _) in envs {
+        cleanup_test_env(env);
+    }
+}
+
+#[test]
+fn test_why_explains_transitive_package() {
+    let test_env = "test-env-why";
+
+    cleanup_test_env(test_env);
+
+    let env_dir = get_test_env_dir().join(test_env);
+    fs::create_dir_all(env_dir.join(".venv")).expect("Failed to create test environment");
+    fs::write(
+        env_dir.join("uv.lock"),
+        r#"
+[[package]]
+name = "test-env-why"
+version = "0.1.0"
+source = { virtual = "." }
+dependencies = [{ name = "requests" }]
+
+[package.dev-dependencies]
+dev = [{ name = "httpx" }]
+
+[[package]]
+name = "requests"
+version = "2.32.3"
+dependencies = [{ name = "certifi" }]
+
+[[package]]
+name = "httpx"
+version = "0.27.0"
+dependencies = [{ name = "certifi" }]
+
+[[package]]
+name = "certifi"
+version = "2024.8.30"
+"#,
+    )
+    .expect("Failed to create uv.lock");
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "why", "certifi", "--env", test_env])
+        .output()
+        .expect("Failed to execute uvup why");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[dependencies] requests 2.32.3 -> certifi 2024.8.30"));
+    assert!(stdout.contains("[group 'dev'] httpx 0.27.0 -> certifi 2024.8.30"));
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "why", "missing", "--env", test_env])
+        .output()
+        .expect("Failed to execute uvup why");
+    assert!(!output.status.success());
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_outdated_against_local_index() {
+    let test_env = "test-env-outdated";
+
+    cleanup_test_env(test_env);
+
+    let env_dir = get_test_env_dir().join(test_env);
+    fs::create_dir_all(env_dir.join(".venv")).expect("Failed to create test environment");
+    fs::write(
+        env_dir.join("pyproject.toml"),
+        r#"[project]
+name = "test-env-outdated"
+version = "0.1.0"
+dependencies = ["requests>=2,<3", "flask==2.0.0", "click>>8"]
+"#,
+    )
+    .expect("Failed to create pyproject.toml");
+    fs::write(
+        env_dir.join("uv.lock"),
+        r#"
+[[package]]
+name = "test-env-outdated"
+version = "0.1.0"
+source = { virtual = "." }
+dependencies = [{ name = "click" }, { name = "flask" }, { name = "requests" }]
+
+[[package]]
+name = "click"
+version = "8.0.0"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "certifi"
+version = "2024.8.30"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "flask"
+version = "2.0.0"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+source = { registry = "https://pypi.org/simple" }
+dependencies = [{ name = "certifi" }, { name = "idna" }]
+
+[[package]]
+name = "idna"
+version = "3.7"
+source = { registry = "https://pypi.org/simple" }
+"#,
+    )
+    .expect("Failed to create uv.lock");
+
+    let wheel = |name: &str, version: &str| serde_json::json!({ "filename": format!("{name}-{version}-py3-none-any.whl") });
+    let index_url = serve_simple_index(vec![
+        (
+            "requests",
+            serde_json::json!({
+                "files": [wheel("requests", "2.31.0"), wheel("requests", "2.32.3"), wheel("requests", "3.0.0a1")]
+            }),
+        ),
+        (
+            "flask",
+            serde_json::json!({ "files": [wheel("flask", "2.0.0"), wheel("flask", "3.0.3")] }),
+        ),
+        (
+            "certifi",
+            serde_json::json!({ "files": [wheel("certifi", "2024.8.30")] }),
+        ),
+        (
+            "click",
+            serde_json::json!({ "files": [wheel("click", "8.0.0"), wheel("click", "8.1.7")] }),
+        ),
+        ("idna", serde_json::Value::Null),
+    ]);
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "outdated", "--env", test_env, "--json"])
+        .args(["--index-url", &index_url])
+        .env("NO_PROXY", "127.0.0.1")
+        .output()
+        .expect("Failed to execute uvup outdated");
+    // The failing index is reported after the rest, and fails the run
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Could not check 1 package(s):\n  idna: "));
+
+    let outdated: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("outdated --json prints JSON");
+    let rows = outdated.as_array().expect("a JSON array");
+    assert_eq!(rows.len(), 3);
+
+    // Specifiers that do not parse cannot be judged
+    assert_eq!(rows[0]["name"], "click");
+    assert_eq!(rows[0]["update"], "unknown");
+
+    assert_eq!(rows[1]["name"], "flask");
+    assert_eq!(rows[1]["latest"], "3.0.3");
+    assert_eq!(rows[1]["update"], "out-of-range");
+
+    // Pre-releases are ignored for packages locked at a final release
+    assert_eq!(rows[2]["name"], "requests");
+    assert_eq!(rows[2]["latest"], "2.32.3");
+    assert_eq!(rows[2]["update"], "in-range");
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_audit_exit_code_and_ignore() {
+    let test_env = "test-env-audit";
+
+    cleanup_test_env(test_env);
+
+    let env_dir = get_test_env_dir().join(test_env);
+    fs::create_dir_all(env_dir.join(".venv")).expect("Failed to create test environment");
+    fs::write(
+        env_dir.join("uv.lock"),
+        r#"
+[[package]]
+name = "test-env-audit"
+version = "0.1.0"
+source = { virtual = "." }
+dependencies = [{ name = "jinja2" }]
+
+[[package]]
+name = "jinja2"
+version = "3.1.2"
+source = { registry = "https://pypi.org/simple" }
+"#,
+    )
+    .expect("Failed to create uv.lock");
+
+    let db = tempfile::tempdir().expect("Failed to create advisory directory");
+    fs::write(
+        db.path().join("GHSA-h5c8-rqwp-cp95.json"),
+        serde_json::json!({
+            "id": "GHSA-h5c8-rqwp-cp95",
+            "aliases": ["CVE-2024-22195"],
+            "database_specific": { "severity": "MODERATE" },
+            "affected": [{
+                "package": { "ecosystem": "PyPI", "name": "jinja2" },
+                "ranges": [{
+                    "type": "ECOSYSTEM",
+                    "events": [{ "introduced": "0" }, { "fixed": "3.1.3" }]
+                }]
+            }]
+        })
+        .to_string(),
+    )
+    .expect("Failed to write advisory");
+    let db_path = db.path().to_str().expect("UTF-8 temp path");
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "audit", "--env", test_env, "--db", db_path])
+        .output()
+        .expect("Failed to execute uvup audit");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("GHSA-h5c8-rqwp-cp95"));
+    assert!(stdout.contains("MODERATE"));
+    assert!(stdout.contains("3.1.3"));
+
+    // Accepted findings can be ignored by any of their ids
+    let output = Command::new("cargo")
+        .args(["run", "--", "audit", "--env", test_env, "--db", db_path])
+        .args(["--ignore", "CVE-2024-22195"])
+        .output()
+        .expect("Failed to execute uvup audit");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("1 ignored"));
+
+    // A directory without advisories must not pass the audit
+    let empty_db = tempfile::tempdir().expect("Failed to create advisory directory");
+    fs::write(empty_db.path().join("broken.json"), "not json").expect("Failed to write file");
+    let output = Command::new("cargo")
+        .args(["run", "--", "audit", "--env", test_env, "--db"])
+        .arg(empty_db.path())
+        .output()
+        .expect("Failed to execute uvup audit");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No PyPI advisories found"));
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_licenses_groups_and_denies() {
+    let test_env = "test-env-licenses";
+
+    cleanup_test_env(test_env);
+
+    let site_packages = if cfg!(windows) {
+        get_test_env_dir()
+            .join(test_env)
+            .join(".venv/Lib/site-packages")
+    } else {
+        get_test_env_dir()
+            .join(test_env)
+            .join(".venv/lib/python3.12/site-packages")
+    };
+    for (dist_info, metadata) in [
+        (
+            "certifi-2024.8.30.dist-info",
+            "Name: certifi\nLicense: MPL-2.0\n",
+        ),
+        (
+            "readline_gpl-1.0.dist-info",
+            "Name: readline-gpl\nClassifier: License :: OSI Approved :: GNU General Public License v3 (GPLv3)\n",
+        ),
+        (
+            "chardet-5.2.0.dist-info",
+            "Name: chardet\nLicense-Expression: LGPL-2.1-or-later\n",
+        ),
+    ] {
+        let dir = site_packages.join(dist_info);
+        fs::create_dir_all(&dir).expect("Failed to create dist-info");
+        fs::write(dir.join("METADATA"), metadata).expect("Failed to write METADATA");
+    }
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "licenses", test_env, "--json"])
+        .output()
+        .expect("Failed to execute uvup licenses");
+    assert!(output.status.success());
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("licenses --json prints JSON");
+    assert_eq!(report["licenses"]["MPL-2.0"][0]["name"], "certifi");
+    assert_eq!(
+        report["licenses"]["GNU General Public License v3 (GPLv3)"][0]["name"],
+        "readline-gpl"
+    );
+
+    // LGPL is not caught by a GPL rule
+    let output = Command::new("cargo")
+        .args(["run", "--", "licenses", test_env, "--deny", "GPL"])
+        .output()
+        .expect("Failed to execute uvup licenses");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("readline-gpl 1.0"));
+    assert!(!stdout.contains("chardet 5.2.0 (LGPL"));
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_licenses_fails_without_synced_venv() {
+    let test_env = "test-env-licenses-unsynced";
+
+    cleanup_test_env(test_env);
+
+    let env_dir = get_test_env_dir().join(test_env);
+    fs::create_dir_all(&env_dir).expect("Failed to create test environment");
+    fs::write(env_dir.join("pyproject.toml"), "[project]\n")
+        .expect("Failed to create pyproject.toml");
+
+    // A deny-list gate must not pass on an environment with nothing installed
+    let output = Command::new("cargo")
+        .args(["run", "--", "licenses", test_env, "--deny", "GPL"])
+        .output()
+        .expect("Failed to execute uvup licenses");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("has no site-packages directory"));
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_sbom_formats() {
+    let test_env = "test-env-sbom";
+
+    cleanup_test_env(test_env);
+
+    let env_dir = get_test_env_dir().join(test_env);
+    let site_packages = if cfg!(windows) {
+        env_dir.join(".venv/Lib/site-packages")
+    } else {
+        env_dir.join(".venv/lib/python3.12/site-packages")
+    };
+    let dist_info = site_packages.join("certifi-2024.8.30.dist-info");
+    fs::create_dir_all(&dist_info).expect("Failed to create test environment");
+    fs::write(
+        dist_info.join("METADATA"),
+        "Name: certifi\nLicense-Expression: MPL-2.0\n",
+    )
+    .expect("Failed to write METADATA");
+    fs::write(
+        env_dir.join("uv.lock"),
+        r#"
+[[package]]
+name = "test-env-sbom"
+version = "0.1.0"
+source = { virtual = "." }
+dependencies = [{ name = "certifi" }]
+
+[[package]]
+name = "certifi"
+version = "2024.8.30"
+source = { registry = "https://pypi.org/simple" }
+sdist = { url = "https://files.example/certifi-2024.8.30.tar.gz", hash = "sha256:0123abcd", size = 1 }
+"#,
+    )
+    .expect("Failed to create uv.lock");
+
+    let sbom = |format: &str| -> serde_json::Value {
+        let output = Command::new("cargo")
+            .args(["run", "--", "sbom", test_env, "--format", format])
+            .output()
+            .expect("Failed to execute uvup sbom");
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).expect("sbom prints JSON")
+    };
+
+    let bom = sbom("cyclonedx-json");
+    assert_eq!(bom["bomFormat"], "CycloneDX");
+    let certifi = &bom["components"][0];
+    assert_eq!(certifi["purl"], "pkg:pypi/certifi@2024.8.30");
+    assert_eq!(certifi["hashes"][0]["content"], "0123abcd");
+    assert_eq!(certifi["licenses"][0]["expression"], "MPL-2.0");
+
+    let spdx = sbom("spdx-json");
+    assert_eq!(spdx["spdxVersion"], "SPDX-2.3");
+    assert!(
+        spdx["relationships"]
+            .as_array()
+            .expect("relationships")
+            .iter()
+            .any(|r| r["relationshipType"] == "DEPENDS_ON"
+                && r["relatedSpdxElement"] == "SPDXRef-certifi-2024.8.30")
+    );
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_package_commands_accept_env_override() {
+    let test_env = "test-env-override";
+
+    cleanup_test_env(test_env);
+
+    let env_dir = get_test_env_dir().join(test_env);
+    fs::create_dir_all(env_dir.join(".venv")).expect("Failed to create test environment");
+    fs::write(
+        env_dir.join("uv.lock"),
+        r#"
+[[package]]
+name = "test-env-override"
+version = "0.1.0"
+source = { virtual = "." }
+dependencies = [{ name = "idna" }]
+
+[[package]]
+name = "idna"
+version = "3.8"
+source = { registry = "https://pypi.org/simple" }
+"#,
+    )
+    .expect("Failed to create uv.lock");
+
+    // UVUP_ENV selects the environment without activating it
+    let output = Command::new("cargo")
+        .args(["run", "--", "packages", "--json"])
+        .env("UVUP_ENV", test_env)
+        .env_remove("UVUP_ACTIVE_ENV")
+        .output()
+        .expect("Failed to execute uvup packages");
+    assert!(output.status.success());
+    let packages: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("packages --json prints JSON");
+    assert_eq!(packages[0]["name"], "idna");
+
+    // UVUP_ENV overriding a different active environment is announced
+    let output = Command::new("cargo")
+        .args(["run", "--", "why", "idna"])
+        .env("UVUP_ENV", test_env)
+        .env("UVUP_ACTIVE_ENV", "test-env-override-other")
+        .output()
+        .expect("Failed to execute uvup why");
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("from UVUP_ENV instead of the active environment"));
+
+    // --env wins over the active environment
+    let output = Command::new("cargo")
+        .args(["run", "--", "why", "idna", "--env", test_env])
+        .env("UVUP_ACTIVE_ENV", "test-env-override-missing")
+        .output()
+        .expect("Failed to execute uvup why");
+    assert!(output.status.success());
+
+    // Only package commands accept --env, and never together with --all
+    for args in [
+        &["list", "--env", test_env][..],
+        &["outdated", "--all", "--env", test_env],
+    ] {
+        let output = Command::new("cargo")
+            .args(["run", "--"])
+            .args(args)
+            .output()
+            .expect("Failed to execute uvup");
+        assert_eq!(output.status.code(), Some(2));
+    }
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "tree", "--env", "test-env-override-missing"])
+        .output()
+        .expect("Failed to execute uvup tree");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("test-env-override-missing"));
+    assert!(!stderr.contains("No active environment"));
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_new_dry_run_filters_dependency_groups() {
+    let test_env = "test-dependency-groups";
+
+    cleanup_test_env(test_env);
+
+    let env_dir = get_test_env_dir().join(test_env);
+    fs::create_dir_all(env_dir.join(".venv")).expect("Failed to create test environment");
+    fs::write(
+        env_dir.join("pyproject.toml"),
+        r#"
+[project]
+name = "test-dependency-groups"
+version = "0.1.0"
+requires-python = ">=3.12"
+dependencies = ["requests"]
+
+[project.optional-dependencies]
+socks = ["pysocks"]
+
+[dependency-groups]
+lint = ["ruff"]
+dev = ["pytest", { include-group = "lint" }]
+"#,
+    )
+    .expect("Failed to create pyproject.toml");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "new",
+            "groups-demo",
+            "--template",
+            test_env,
+            "--exclude",
+            "ruff",
+            "--dry-run",
+        ])
+        .output()
+        .expect("Failed to execute uvup new");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Optional dependencies:\n  [socks]: No changes"));
+    assert!(stdout.contains("Dependency groups:\n  [dev]: No changes"));
+    assert!(stdout.contains("[lint]: Modified (0 packages)"));
+
+    // Extras and dependency groups are separate tables, so the flags conflict
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "add",
+            "--optional",
+            "socks",
+            "--dev",
+            "pysocks",
+        ])
+        .output()
+        .expect("Failed to execute uvup add");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+
+    cleanup_test_env(test_env);
+}
+
+#[test]
+fn test_prompt_without_active_environment() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "prompt"])
+        .env_remove("UVUP_ACTIVE_ENV")
+        .output()
+        .expect("Failed to execute uvup prompt");
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
 #[test]
 fn test_create_list_delete_workflow() {
     let test_env = "test-integration-env";